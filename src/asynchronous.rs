@@ -0,0 +1,79 @@
+use std::sync::OnceLock;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::{Secret, SecretConfig, SecretError, SecretResult};
+
+static CONCURRENCY_LIMIT: OnceLock<Semaphore> = OnceLock::new();
+
+impl Secret {
+    pub fn limit_async_concurrency(permits: usize) -> SecretResult<()> {
+        CONCURRENCY_LIMIT
+            .set(Semaphore::new(permits))
+            .map_err(|_| SecretError::ConcurrencyLimitAlreadySet)
+    }
+
+    pub async fn new_async<S: AsRef<[u8]> + Send + 'static>(secret: S) -> SecretResult<Self> {
+        Self::new_with_config_async(secret, SecretConfig::default()).await
+    }
+
+    pub async fn new_with_config_async<S: AsRef<[u8]> + Send + 'static>(
+        secret: S,
+        config: SecretConfig,
+    ) -> SecretResult<Self> {
+        Self::spawn_blocking(move || Self::new_with_config(secret, config)).await?
+    }
+
+    pub async fn verify_async<S: AsRef<[u8]> + Send + 'static>(&self, secret: S) -> bool {
+        let this = self.clone();
+        Self::spawn_blocking(move || this.verify(secret))
+            .await
+            .unwrap_or(false)
+    }
+
+    pub(crate) async fn spawn_blocking<T, F>(f: F) -> SecretResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let permit = Self::concurrency_limit()
+            .acquire()
+            .await
+            .expect("concurrency limit is never closed");
+        Self::run_blocking(permit, f).await
+    }
+
+    pub(crate) async fn try_spawn_blocking<T, F>(f: F) -> SecretResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let permit = Self::concurrency_limit()
+            .try_acquire()
+            .map_err(|_| SecretError::PoolExhausted)?;
+        Self::run_blocking(permit, f).await
+    }
+
+    async fn run_blocking<T, F>(permit: SemaphorePermit<'static>, f: F) -> SecretResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        tokio::task::spawn_blocking(move || {
+            let result = f();
+            drop(permit);
+            result
+        })
+        .await
+        .map_err(SecretError::Join)
+    }
+
+    fn concurrency_limit() -> &'static Semaphore {
+        CONCURRENCY_LIMIT.get_or_init(|| {
+            let permits = std::thread::available_parallelism()
+                .map(|permits| permits.get())
+                .unwrap_or(1);
+            Semaphore::new(permits)
+        })
+    }
+}
@@ -1,6 +1,6 @@
 pub use base64::Engine;
 
-use crate::{Secret, SecretError, SecretResult};
+use crate::{Secret, SecretConfig, SecretError, SecretResult};
 
 #[allow(non_upper_case_globals)]
 pub const b64Engine: base64::engine::GeneralPurpose = base64::engine::GeneralPurpose::new(
@@ -14,8 +14,15 @@ impl Secret {
     }
 
     pub fn load_from_base64<S: AsRef<[u8]>>(s: S) -> SecretResult<Self> {
+        Self::load_from_base64_with_config(s, &SecretConfig::default())
+    }
+
+    pub fn load_from_base64_with_config<S: AsRef<[u8]>>(
+        s: S,
+        config: &SecretConfig,
+    ) -> SecretResult<Self> {
         let encoded = b64Engine.decode(s).map_err(SecretError::InvalidBase64)?;
         let raw = String::from_utf8(encoded).map_err(SecretError::InvalidUtf8)?;
-        Self::load(raw)
+        Self::load_with_config(raw, config)
     }
 }
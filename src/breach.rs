@@ -0,0 +1,219 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use sha1::{Digest, Sha1};
+
+use crate::{SecretError, SecretResult};
+
+const SHA1_LEN: usize = 20;
+const HIBP_PREFIX_LEN: usize = 5;
+
+#[derive(Clone)]
+pub struct BreachCorpus(Arc<Corpus>);
+
+enum Corpus {
+    Hashes(HashSet<[u8; SHA1_LEN]>),
+    Ranges(PathBuf),
+    Bloom(BloomFilter),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    hashes: u32,
+}
+
+impl BreachCorpus {
+    /// Loads every digest into memory, which takes tens of GiB for the full corpus; use
+    /// `from_hibp_dir` or a `BloomFilter` at that scale.
+    pub fn from_hibp<R: BufRead>(reader: R) -> SecretResult<Self> {
+        let hashes = hibp_digests("", reader).collect::<SecretResult<_>>()?;
+        Ok(BreachCorpus(Arc::new(Corpus::Hashes(hashes))))
+    }
+
+    pub fn from_hibp_file<P: AsRef<Path>>(path: P) -> SecretResult<Self> {
+        let file = File::open(path).map_err(SecretError::BreachCorpus)?;
+        Self::from_hibp(BufReader::new(file))
+    }
+
+    // Range files are the k-anonymity responses, holding only the suffixes for one 5 digit prefix.
+    pub fn from_hibp_ranges<I, R>(ranges: I) -> SecretResult<Self>
+    where
+        I: IntoIterator<Item = (String, R)>,
+        R: BufRead,
+    {
+        let mut hashes = HashSet::new();
+        for (prefix, reader) in ranges {
+            if prefix.len() != HIBP_PREFIX_LEN {
+                return Err(invalid_corpus("HIBP range prefix must be 5 hex digits"));
+            }
+            for digest in hibp_digests(&prefix, reader) {
+                hashes.insert(digest?);
+            }
+        }
+        Ok(BreachCorpus(Arc::new(Corpus::Hashes(hashes))))
+    }
+
+    /// Looks secrets up in a directory of range files named by their prefix (`5BAA6` or
+    /// `5BAA6.txt`), reading only the one file matching each secret.
+    pub fn from_hibp_dir<P: AsRef<Path>>(path: P) -> SecretResult<Self> {
+        let path = path.as_ref();
+        if !path.metadata().map_err(SecretError::BreachCorpus)?.is_dir() {
+            return Err(invalid_corpus("HIBP range directory is not a directory"));
+        }
+        Ok(BreachCorpus(Arc::new(Corpus::Ranges(path.to_path_buf()))))
+    }
+
+    pub fn from_bloom(filter: BloomFilter) -> Self {
+        BreachCorpus(Arc::new(Corpus::Bloom(filter)))
+    }
+
+    pub fn contains(&self, secret: &[u8]) -> bool {
+        let digest = sha1(secret);
+        match self.0.as_ref() {
+            Corpus::Hashes(hashes) => hashes.contains(&digest),
+            Corpus::Ranges(dir) => range_contains(dir, &digest),
+            Corpus::Bloom(filter) => filter.contains_digest(&digest),
+        }
+    }
+}
+
+impl Debug for BreachCorpus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BreachCorpus(..)")
+    }
+}
+
+impl BloomFilter {
+    pub fn new(bits: usize, hashes: u32) -> Self {
+        BloomFilter {
+            bits: vec![0; bits.div_ceil(u64::BITS as usize).max(1)],
+            hashes: hashes.max(1),
+        }
+    }
+
+    pub fn from_hibp<R: BufRead>(reader: R, bits: usize, hashes: u32) -> SecretResult<Self> {
+        let mut filter = Self::new(bits, hashes);
+        for digest in hibp_digests("", reader) {
+            filter.insert_digest(&digest?);
+        }
+        Ok(filter)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SecretResult<Self> {
+        let (hashes, bits) = bytes
+            .split_first_chunk::<4>()
+            .ok_or_else(|| invalid_corpus("bloom filter is truncated"))?;
+        if bits.is_empty() || bits.len() % 8 != 0 {
+            return Err(invalid_corpus("bloom filter is truncated"));
+        }
+
+        Ok(BloomFilter {
+            bits: bits
+                .chunks_exact(8)
+                .map(|word| u64::from_le_bytes(word.try_into().expect("chunk of 8 bytes")))
+                .collect(),
+            hashes: u32::from_le_bytes(*hashes).max(1),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.hashes.to_le_bytes().to_vec();
+        bytes.extend(self.bits.iter().flat_map(|word| word.to_le_bytes()));
+        bytes
+    }
+
+    pub fn insert(&mut self, secret: &[u8]) {
+        self.insert_digest(&sha1(secret));
+    }
+
+    pub fn contains(&self, secret: &[u8]) -> bool {
+        self.contains_digest(&sha1(secret))
+    }
+
+    fn insert_digest(&mut self, digest: &[u8; SHA1_LEN]) {
+        for bit in self.positions(digest) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    fn contains_digest(&self, digest: &[u8; SHA1_LEN]) -> bool {
+        self.positions(digest)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    // Double hashing over the two halves of the SHA-1 digest.
+    fn positions(&self, digest: &[u8; SHA1_LEN]) -> impl Iterator<Item = usize> {
+        let h1 = u64::from_le_bytes(digest[..8].try_into().expect("slice of 8 bytes"));
+        let h2 = u64::from_le_bytes(digest[8..16].try_into().expect("slice of 8 bytes"));
+        let len = (self.bits.len() * 64) as u64;
+        (0..u64::from(self.hashes))
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len) as usize)
+    }
+}
+
+// A missing range file means no breached hash has that prefix; unreadable lines are skipped.
+fn range_contains(dir: &Path, digest: &[u8; SHA1_LEN]) -> bool {
+    let prefix = format!("{:02X}{:02X}{:X}", digest[0], digest[1], digest[2] >> 4);
+    let Some(file) = [prefix.clone(), format!("{prefix}.txt")]
+        .iter()
+        .find_map(|name| File::open(dir.join(name)).ok())
+    else {
+        return false;
+    };
+    let found = hibp_digests(&prefix, BufReader::new(file))
+        .filter_map(Result::ok)
+        .any(|candidate| candidate == *digest);
+    found
+}
+
+fn sha1(secret: &[u8]) -> [u8; SHA1_LEN] {
+    Sha1::digest(secret).into()
+}
+
+fn hibp_digests<'a, R: BufRead + 'a>(
+    prefix: &'a str,
+    reader: R,
+) -> impl Iterator<Item = SecretResult<[u8; SHA1_LEN]>> + 'a {
+    reader.lines().filter_map(move |line| {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => return Some(Err(SecretError::BreachCorpus(err))),
+        };
+        let hash = line.split(':').next().unwrap_or_default().trim();
+        if hash.is_empty() {
+            return None;
+        }
+        Some(decode_sha1(prefix, hash))
+    })
+}
+
+fn decode_sha1(prefix: &str, suffix: &str) -> SecretResult<[u8; SHA1_LEN]> {
+    let mut digits = prefix.bytes().chain(suffix.bytes());
+    let mut digest = [0u8; SHA1_LEN];
+    for byte in digest.iter_mut() {
+        let (Some(high), Some(low)) = (digits.next(), digits.next()) else {
+            return Err(invalid_corpus("SHA-1 hash is too short"));
+        };
+        *byte = (hex_digit(high)? << 4) | hex_digit(low)?;
+    }
+    if digits.next().is_some() {
+        return Err(invalid_corpus("SHA-1 hash is too long"));
+    }
+    Ok(digest)
+}
+
+fn hex_digit(digit: u8) -> SecretResult<u8> {
+    char::from(digit)
+        .to_digit(16)
+        .map(|digit| digit as u8)
+        .ok_or_else(|| invalid_corpus("SHA-1 hash is not hexadecimal"))
+}
+
+fn invalid_corpus(reason: &str) -> SecretError {
+    SecretError::BreachCorpus(std::io::Error::new(std::io::ErrorKind::InvalidData, reason))
+}
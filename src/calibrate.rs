@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use argon2::Argon2;
+
+use crate::{Algorithm, Params, SecretConfig, SecretError, SecretResult, Version};
+
+const SAMPLES: usize = 3;
+
+impl SecretConfig {
+    pub fn calibrate(target: Duration, max_memory_kib: u32) -> SecretResult<Self> {
+        let mut m_cost = max_memory_kib;
+        let mut elapsed = Self::measure(m_cost, Params::MIN_T_COST)?;
+        while elapsed > target {
+            m_cost /= 2;
+            if m_cost < Params::MIN_M_COST {
+                return Err(SecretError::CalibrationTarget(target));
+            }
+            elapsed = Self::measure(m_cost, Params::MIN_T_COST)?;
+        }
+
+        // Time grows roughly linearly with t_cost, so twice the estimate bounds the search.
+        let estimate = target.as_nanos() / elapsed.as_nanos().max(1);
+        let mut lower = Params::MIN_T_COST;
+        let mut upper = u32::try_from(estimate)
+            .unwrap_or(u32::MAX)
+            .saturating_mul(2)
+            .max(Params::MIN_T_COST);
+        while lower < upper {
+            let t_cost = lower + (upper - lower).div_ceil(2);
+            if Self::measure(m_cost, t_cost)? > target {
+                upper = t_cost - 1;
+            } else {
+                lower = t_cost;
+            }
+        }
+
+        Ok(SecretConfig {
+            params: Self::params(m_cost, lower)?,
+            ..Default::default()
+        })
+    }
+
+    // The fastest of a few runs, to filter out scheduling noise.
+    fn measure(m_cost: u32, t_cost: u32) -> SecretResult<Duration> {
+        let hasher = Argon2::new(
+            Algorithm::default(),
+            Version::default(),
+            Self::params(m_cost, t_cost)?,
+        );
+        let mut output = [0u8; Params::DEFAULT_OUTPUT_LEN];
+
+        let mut fastest = Duration::MAX;
+        for _ in 0..SAMPLES {
+            let started = Instant::now();
+            hasher
+                .hash_password_into(b"calibration", &[0u8; 16], &mut output)
+                .map_err(|err| SecretError::Hashing(err.into()))?;
+            fastest = fastest.min(started.elapsed());
+        }
+        Ok(fastest)
+    }
+
+    fn params(m_cost: u32, t_cost: u32) -> SecretResult<Params> {
+        Params::new(m_cost, t_cost, Params::DEFAULT_P_COST, None)
+            .map_err(|err| SecretError::InvalidParams(err.into()))
+    }
+}
@@ -1,8 +1,222 @@
+use argon2::password_hash::Salt;
 pub use argon2::{Algorithm, Params, Version};
+use argon2::{AssociatedData, KeyId, ParamsBuilder, PasswordHash};
 
-#[derive(Debug, Default)]
+use crate::normalization::Prepared;
+use crate::{
+    Keyring, LoadPolicy, Normalization, PasswordPolicy, Pepper, PreHash, SecretError, SecretResult,
+};
+
+#[derive(Debug, Default, Clone, Copy)]
+pub enum Backend {
+    #[default]
+    Argon2,
+    #[cfg(feature = "bcrypt")]
+    Bcrypt { cost: u32 },
+    #[cfg(feature = "scrypt")]
+    Scrypt(scrypt::Params),
+    #[cfg(feature = "pbkdf2")]
+    Pbkdf2(pbkdf2::Params),
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct SecretConfig {
+    pub backend: Backend,
     pub algorithm: Algorithm,
     pub version: Version,
     pub params: Params,
+    /// Salt length in bytes, defaults to 16 and is capped at 48 by the PHC string format.
+    pub salt_len: Option<usize>,
+    /// Applied to UTF-8 input when hashing and verifying, recorded in the Argon2 `data` parameter.
+    pub normalization: Normalization,
+    /// Compacts the input before Argon2, recorded in the `data` parameter like `normalization`.
+    pub pre_hash: PreHash,
+    /// Longer inputs are rejected before any hashing work, kept by secrets hashed or loaded with it.
+    pub max_input_len: Option<usize>,
+    /// Server-side secret key mixed into Argon2 hashes, ignored by other backends.
+    pub pepper: Option<Pepper>,
+    /// Rotatable peppers, takes precedence over `pepper` when hashing new secrets.
+    pub keyring: Option<Keyring>,
+    /// Enforced when loading secrets, falls back to the installed `LoadPolicy`.
+    pub load_policy: Option<LoadPolicy>,
+    /// Checked before hashing new secrets, falls back to the installed `PasswordPolicy`.
+    pub password_policy: Option<PasswordPolicy>,
+}
+
+impl SecretConfig {
+    #[allow(unreachable_patterns)]
+    pub(crate) fn is_weaker_than(&self, target: &SecretConfig) -> bool {
+        match (self.backend, target.backend) {
+            (Backend::Argon2, Backend::Argon2) => {
+                self.algorithm != target.algorithm
+                    || self.version < target.version
+                    || self.params.m_cost() < target.params.m_cost()
+                    || self.params.t_cost() < target.params.t_cost()
+                    || self.params.p_cost() < target.params.p_cost()
+                    || Self::output_len(&self.params) < Self::output_len(&target.params)
+                    || self.salt_len < target.salt_len
+                    || self.normalization != target.normalization
+                    || self.pre_hash != target.pre_hash
+                    || target
+                        .keyring
+                        .as_ref()
+                        .is_some_and(|keyring| self.params.keyid() != keyring.current())
+            }
+            #[cfg(feature = "bcrypt")]
+            (Backend::Bcrypt { cost }, Backend::Bcrypt { cost: target_cost }) => cost < target_cost,
+            #[cfg(feature = "scrypt")]
+            (Backend::Scrypt(params), Backend::Scrypt(target_params)) => {
+                params.log_n() < target_params.log_n()
+                    || params.r() < target_params.r()
+                    || params.p() < target_params.p()
+            }
+            #[cfg(feature = "pbkdf2")]
+            (Backend::Pbkdf2(params), Backend::Pbkdf2(target_params)) => {
+                params.rounds < target_params.rounds
+                    || params.output_length < target_params.output_length
+            }
+            _ => true,
+        }
+    }
+
+    pub(crate) fn hashing_pepper(&self) -> Option<&Pepper> {
+        match &self.keyring {
+            Some(keyring) => Some(keyring.current_pepper()),
+            None => self.pepper.as_ref(),
+        }
+    }
+
+    pub(crate) fn enforce_password_policy(&self, secret: &[u8]) -> SecretResult<()> {
+        match self
+            .password_policy
+            .as_ref()
+            .or(PasswordPolicy::installed())
+        {
+            Some(policy) => policy.enforce(secret),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_input_len(&self, secret: &[u8]) -> SecretResult<()> {
+        match self.max_input_len {
+            Some(max_input_len) if secret.len() > max_input_len => {
+                Err(SecretError::InputTooLong(max_input_len))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn prepare<'a>(&self, secret: &'a [u8], salt: &[u8]) -> SecretResult<Prepared<'a>> {
+        let secret = self.normalization.prepare(secret)?;
+        Ok(self.pre_hash.apply(secret, salt))
+    }
+
+    pub(crate) fn hashing_params(&self) -> SecretResult<Params> {
+        let markers = [self.normalization.marker(), self.pre_hash.marker()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let marker = (!markers.is_empty()).then(|| markers.join(&b','));
+        if self.keyring.is_none() && marker.is_none() {
+            return Ok(self.params.clone());
+        }
+
+        let keyid = match &self.keyring {
+            Some(keyring) => keyring.current_key_id(),
+            None => KeyId::new(self.params.keyid())
+                .map_err(|err| SecretError::InvalidParams(err.into()))?,
+        };
+        let data = AssociatedData::new(marker.as_deref().unwrap_or(self.params.data()))
+            .map_err(|err| SecretError::InvalidParams(err.into()))?;
+        let mut builder = ParamsBuilder::new();
+        builder
+            .m_cost(self.params.m_cost())
+            .t_cost(self.params.t_cost())
+            .p_cost(self.params.p_cost())
+            .keyid(keyid)
+            .data(data);
+        if let Some(output_len) = self.params.output_len() {
+            builder.output_len(output_len);
+        }
+        builder
+            .build()
+            .map_err(|err| SecretError::InvalidParams(err.into()))
+    }
+
+    // Without a keyring, key id hashes are left unpeppered until one is attached.
+    pub(crate) fn pepper_for(&self, key_id: &[u8]) -> SecretResult<Option<Pepper>> {
+        if let Some(pepper) = self
+            .keyring
+            .as_ref()
+            .and_then(|keyring| keyring.get(key_id))
+        {
+            return Ok(Some(pepper.clone()));
+        }
+        if key_id.is_empty() {
+            Ok(self.pepper.clone())
+        } else if self.keyring.is_some() {
+            Err(SecretError::UnknownKeyId)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn output_len(params: &Params) -> usize {
+        params.output_len().unwrap_or(Params::DEFAULT_OUTPUT_LEN)
+    }
+}
+
+impl<'a> TryFrom<&'a PasswordHash<'a>> for SecretConfig {
+    type Error = SecretError;
+
+    fn try_from(hash: &'a PasswordHash<'a>) -> Result<Self, Self::Error> {
+        #[cfg(feature = "scrypt")]
+        if hash.algorithm == scrypt::ALG_ID {
+            let params = scrypt::Params::try_from(hash).map_err(SecretError::InvalidParams)?;
+            return Ok(SecretConfig {
+                backend: Backend::Scrypt(params),
+                ..Default::default()
+            });
+        }
+
+        #[cfg(feature = "pbkdf2")]
+        if pbkdf2::Algorithm::try_from(hash.algorithm).is_ok() {
+            let params = pbkdf2::Params::try_from(hash).map_err(SecretError::InvalidParams)?;
+            return Ok(SecretConfig {
+                backend: Backend::Pbkdf2(params),
+                ..Default::default()
+            });
+        }
+
+        let algorithm =
+            Algorithm::try_from(hash.algorithm).map_err(SecretError::InvalidAlgorithm)?;
+        let version = match hash.version {
+            Some(ver) => Version::try_from(ver).map_err(SecretError::InvalidVersion)?,
+            None => Version::default(),
+        };
+        let params = Params::try_from(hash).map_err(SecretError::InvalidParams)?;
+        let salt_len = match hash.salt {
+            Some(salt) => Some(
+                salt.decode_b64(&mut [0u8; Salt::MAX_LENGTH])
+                    .map_err(SecretError::CorruptHash)?
+                    .len(),
+            ),
+            None => None,
+        };
+
+        Ok(SecretConfig {
+            backend: Backend::Argon2,
+            algorithm,
+            version,
+            normalization: Normalization::from_marker(params.data()),
+            pre_hash: PreHash::from_marker(params.data())?,
+            max_input_len: None,
+            params,
+            salt_len,
+            pepper: None,
+            keyring: None,
+            load_policy: None,
+            password_policy: None,
+        })
+    }
 }
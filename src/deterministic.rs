@@ -0,0 +1,30 @@
+use argon2::password_hash::SaltString;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::hash::SecretHash;
+use crate::{Secret, SecretConfig, SecretError, SecretResult};
+
+// Reproducible hashes are meant for fixtures and snapshot tests, never for production secrets.
+impl Secret {
+    pub fn new_with_rng<S: AsRef<[u8]>, R: RngCore + CryptoRng>(
+        secret: S,
+        config: SecretConfig,
+        rng: &mut R,
+    ) -> SecretResult<Self> {
+        config.check_input_len(secret.as_ref())?;
+        config.enforce_password_policy(secret.as_ref())?;
+        let salt = SecretHash::salt(&config, rng)?;
+        Self::hash_secret_with_salt(secret.as_ref(), config, &salt)
+    }
+
+    pub fn new_with_salt<S: AsRef<[u8]>>(
+        secret: S,
+        config: SecretConfig,
+        salt: &[u8],
+    ) -> SecretResult<Self> {
+        config.check_input_len(secret.as_ref())?;
+        config.enforce_password_policy(secret.as_ref())?;
+        let salt = SaltString::encode_b64(salt).map_err(SecretError::Hashing)?;
+        Self::hash_secret_with_salt(secret.as_ref(), config, &salt)
+    }
+}
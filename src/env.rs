@@ -0,0 +1,84 @@
+use std::env::{self, VarError};
+use std::str::FromStr;
+
+use crate::{
+    Algorithm, Normalization, Params, Pepper, PreHash, SecretConfig, SecretError, SecretResult,
+};
+
+impl SecretConfig {
+    pub fn from_env(prefix: &str) -> SecretResult<Self> {
+        let var = |suffix: &str| format!("{prefix}_{suffix}");
+
+        let algorithm = match Self::env_var(&var("ALGORITHM"))? {
+            Some(algorithm) => Algorithm::new(algorithm)
+                .map_err(|_| SecretError::InvalidEnvVar(var("ALGORITHM")))?,
+            None => Algorithm::default(),
+        };
+        let m_cost = Self::parse_env_var(&var("M_COST"))?.unwrap_or(Params::DEFAULT_M_COST);
+        let t_cost = Self::parse_env_var(&var("T_COST"))?.unwrap_or(Params::DEFAULT_T_COST);
+        let p_cost = Self::parse_env_var(&var("P_COST"))?.unwrap_or(Params::DEFAULT_P_COST);
+        let output_len = Self::parse_env_var(&var("OUTPUT_LEN"))?;
+        let params = Params::new(m_cost, t_cost, p_cost, output_len).map_err(|err| {
+            let suffix = match err {
+                argon2::Error::MemoryTooLittle | argon2::Error::MemoryTooMuch => "M_COST",
+                argon2::Error::TimeTooSmall => "T_COST",
+                argon2::Error::OutputTooShort | argon2::Error::OutputTooLong => "OUTPUT_LEN",
+                _ => "P_COST",
+            };
+            SecretError::InvalidEnvVar(var(suffix))
+        })?;
+        let salt_len = Self::parse_env_var(&var("SALT_LEN"))?;
+        let normalization = match Self::env_var(&var("NORMALIZATION"))? {
+            Some(name) => Normalization::from_name(name.trim())
+                .ok_or_else(|| SecretError::InvalidEnvVar(var("NORMALIZATION")))?,
+            None => Normalization::None,
+        };
+        let pre_hash = match Self::env_var(&var("PRE_HASH"))? {
+            Some(name) => PreHash::from_name(name.trim())
+                .ok_or_else(|| SecretError::InvalidEnvVar(var("PRE_HASH")))?,
+            None => PreHash::None,
+        };
+        let max_input_len = Self::parse_env_var(&var("MAX_INPUT_LEN"))?;
+        let pepper = match Self::env_var(&var("PEPPER_FILE"))? {
+            Some(path) => {
+                let mut pepper = std::fs::read(path)
+                    .map_err(|_| SecretError::InvalidEnvVar(var("PEPPER_FILE")))?;
+                if pepper.ends_with(b"\n") {
+                    pepper.pop();
+                }
+                Some(Pepper::from(pepper))
+            }
+            None => None,
+        };
+
+        Ok(SecretConfig {
+            algorithm,
+            params,
+            salt_len,
+            normalization,
+            pre_hash,
+            max_input_len,
+            pepper,
+            ..Default::default()
+        })
+    }
+
+    fn env_var(name: &str) -> SecretResult<Option<String>> {
+        match env::var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(SecretError::InvalidEnvVar(name.to_string())),
+        }
+    }
+
+    fn parse_env_var<T: FromStr>(name: &str) -> SecretResult<Option<T>> {
+        Self::env_var(name)?
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| SecretError::InvalidEnvVar(name.to_string()))
+            })
+            .transpose()
+    }
+}
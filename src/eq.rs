@@ -1,14 +1,64 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use subtle::{Choice, ConstantTimeEq};
+
 use crate::Secret;
 
+impl ConstantTimeEq for Secret {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        ct_eq_bytes(
+            self.secret.canonical().bytes(),
+            other.secret.canonical().bytes(),
+        )
+    }
+}
+
 impl PartialEq for Secret {
     fn eq(&self, other: &Self) -> bool {
-        self.secret.eq(&other.secret)
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Secret {}
+
+impl Hash for Secret {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.secret.canonical().hash(state);
     }
 }
 
+// Ordering is inherently data dependent, use `ct_eq` where only equality is needed.
+impl Ord for Secret {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.secret.canonical().cmp(&other.secret.canonical())
+    }
+}
+
+impl PartialOrd for Secret {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Kept for existing users of the opt-in `eq` feature. It runs a full verification inside `==` and
+// reads errors as a mismatch, so new code should call `verify` or `try_verify` explicitly.
 #[cfg(feature = "eq")]
 impl<S: AsRef<[u8]>> PartialEq<S> for Secret {
     fn eq(&self, other: &S) -> bool {
         self.verify(other)
     }
 }
+
+// Only the length is allowed to short-circuit, every byte pair is visited otherwise.
+pub(crate) fn ct_eq_bytes<A, B>(lhs: A, rhs: B) -> Choice
+where
+    A: ExactSizeIterator<Item = u8>,
+    B: ExactSizeIterator<Item = u8>,
+{
+    if lhs.len() != rhs.len() {
+        return Choice::from(0);
+    }
+    lhs.zip(rhs)
+        .fold(Choice::from(1), |equal, (lhs, rhs)| equal & lhs.ct_eq(&rhs))
+}
@@ -10,6 +10,52 @@ pub enum SecretError {
     InvalidVersion(argon2::Error),
     #[error("Invalid hasher parameters")]
     InvalidParams(argon2::password_hash::Error),
+    #[error("Invalid pepper")]
+    InvalidPepper(argon2::Error),
+    #[error("Invalid pepper key identifier")]
+    InvalidKeyId(argon2::Error),
+    #[error("Unknown pepper key identifier")]
+    UnknownKeyId,
+    #[error("Secret hash violates the load policy on {0}")]
+    LoadPolicy(&'static str),
+    #[error("Load policy is already installed")]
+    LoadPolicyAlreadyInstalled,
+    #[error("Secret violates the password policy: {0}")]
+    PasswordPolicy(crate::PasswordViolation),
+    #[error("Password policy is already installed")]
+    PasswordPolicyAlreadyInstalled,
+    #[error("Payload config is already installed")]
+    PayloadConfigAlreadyInstalled,
+    #[cfg(feature = "breach")]
+    #[error("Failed to load the breach corpus")]
+    BreachCorpus(std::io::Error),
+    #[error("Secret is longer than {0} bytes")]
+    InputTooLong(usize),
+    #[error("Secret hash is pre-hashed, which requires the prehash feature")]
+    PreHashUnavailable,
+    #[error("Failed to normalize secret: {0}")]
+    Normalization(&'static str),
+    #[error("{0} is only supported by the Argon2 backend")]
+    UnsupportedByBackend(&'static str),
+    #[error("Corrupt secret hash")]
+    CorruptHash(argon2::password_hash::Error),
+    #[error("Failed to verify secret")]
+    Verification(argon2::password_hash::Error),
+    #[cfg(feature = "bcrypt")]
+    #[error("Bcrypt failure")]
+    Bcrypt(bcrypt::BcryptError),
+    #[error("No hasher parameters meet the latency target of {0:?}")]
+    CalibrationTarget(std::time::Duration),
+    #[error("Invalid environment variable {0}")]
+    InvalidEnvVar(String),
+    #[error("Secret pool is exhausted")]
+    PoolExhausted,
+    #[cfg(feature = "async")]
+    #[error("Concurrency limit is already set")]
+    ConcurrencyLimitAlreadySet,
+    #[cfg(feature = "async")]
+    #[error("Background hashing task failed")]
+    Join(tokio::task::JoinError),
     #[cfg(feature = "base64")]
     #[error("Invalid Base64")]
     InvalidBase64(base64::DecodeError),
@@ -17,3 +63,24 @@ pub enum SecretError {
     #[error("Invalid UTF-8")]
     InvalidUtf8(std::string::FromUtf8Error),
 }
+
+impl SecretError {
+    pub(crate) fn verification(err: argon2::password_hash::Error) -> Self {
+        use argon2::password_hash::Error;
+
+        match err {
+            Error::Algorithm => SecretError::InvalidAlgorithm(err),
+            Error::ParamNameDuplicated
+            | Error::ParamNameInvalid
+            | Error::ParamValueInvalid(_)
+            | Error::ParamsMaxExceeded => SecretError::InvalidParams(err),
+            Error::B64Encoding(_)
+            | Error::OutputSize { .. }
+            | Error::PhcStringField
+            | Error::PhcStringTrailingData
+            | Error::SaltInvalid(_)
+            | Error::Version => SecretError::CorruptHash(err),
+            _ => SecretError::Verification(err),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+use argon2::password_hash::errors::InvalidValue;
+use argon2::password_hash::{self, ParamsString, PasswordHashString, Salt, SaltString};
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use rand_core::RngCore;
+
+use crate::{
+    Algorithm, Backend, Normalization, Params, Pepper, PreHash, SecretConfig, SecretError,
+    SecretResult, Version,
+};
+
+#[derive(Clone)]
+pub(crate) enum SecretHash {
+    Phc(PasswordHashString),
+    #[cfg(feature = "bcrypt")]
+    Bcrypt(String),
+}
+
+impl SecretHash {
+    pub(crate) fn generate(
+        secret: &[u8],
+        config: &SecretConfig,
+        salt: &SaltString,
+    ) -> SecretResult<Self> {
+        // Other backends cannot record normalization or pre-hashing in the hash, so verify could
+        // never apply them.
+        if !matches!(config.backend, Backend::Argon2) {
+            if config.normalization != Normalization::None {
+                return Err(SecretError::UnsupportedByBackend("normalization"));
+            }
+            if config.pre_hash != PreHash::None {
+                return Err(SecretError::UnsupportedByBackend("pre_hash"));
+            }
+        }
+
+        let secret_hash = match config.backend {
+            Backend::Argon2 => Self::hasher(
+                config.hashing_pepper(),
+                config.algorithm,
+                config.version,
+                config.hashing_params()?,
+            )?
+            .hash_password(
+                &config.prepare(secret, &Self::decode_salt(salt.as_salt())?)?,
+                salt,
+            ),
+            #[cfg(feature = "bcrypt")]
+            Backend::Bcrypt { cost } => {
+                let mut bcrypt_salt = [0u8; 16];
+                let decoded = salt
+                    .decode_b64(&mut bcrypt_salt)
+                    .map_err(SecretError::Hashing)?
+                    .len();
+                if decoded != bcrypt_salt.len() {
+                    return Err(SecretError::Hashing(password_hash::Error::SaltInvalid(
+                        InvalidValue::TooShort,
+                    )));
+                }
+                return bcrypt::hash_with_salt(secret, cost, bcrypt_salt)
+                    .map(|parts| {
+                        SecretHash::Bcrypt(parts.format_for_version(bcrypt::Version::TwoB))
+                    })
+                    .map_err(SecretError::Bcrypt);
+            }
+            #[cfg(feature = "scrypt")]
+            Backend::Scrypt(params) => {
+                scrypt::Scrypt.hash_password_customized(secret, None, None, params, salt)
+            }
+            #[cfg(feature = "pbkdf2")]
+            Backend::Pbkdf2(params) => {
+                pbkdf2::Pbkdf2.hash_password_customized(secret, None, None, params, salt)
+            }
+        };
+
+        Ok(SecretHash::Phc(
+            secret_hash.map_err(SecretError::Hashing)?.serialize(),
+        ))
+    }
+
+    pub(crate) fn salt<R: RngCore>(config: &SecretConfig, rng: &mut R) -> SecretResult<SaltString> {
+        let mut salt = [0u8; Salt::MAX_LENGTH];
+        let salt = salt
+            .get_mut(..config.salt_len.unwrap_or(Salt::RECOMMENDED_LENGTH))
+            .ok_or(SecretError::Hashing(password_hash::Error::SaltInvalid(
+                InvalidValue::TooLong,
+            )))?;
+        rng.fill_bytes(salt);
+        SaltString::encode_b64(salt).map_err(SecretError::Hashing)
+    }
+
+    pub(crate) fn decode_salt(salt: Salt<'_>) -> SecretResult<Vec<u8>> {
+        salt.decode_b64(&mut [0u8; Salt::MAX_LENGTH])
+            .map(<[u8]>::to_vec)
+            .map_err(SecretError::CorruptHash)
+    }
+
+    pub(crate) fn load(secretized: &str) -> SecretResult<Self> {
+        #[cfg(feature = "bcrypt")]
+        if secretized.starts_with("$2") {
+            return secretized
+                .parse::<bcrypt::HashParts>()
+                .map(|_| SecretHash::Bcrypt(secretized.to_string()))
+                .map_err(SecretError::Bcrypt);
+        }
+
+        let secret_hash = PasswordHash::new(secretized).map_err(SecretError::HashParsing)?;
+        SecretConfig::try_from(&secret_hash)?;
+
+        Ok(SecretHash::Phc(secret_hash.serialize()))
+    }
+
+    #[allow(unreachable_patterns)]
+    pub(crate) fn verify(&self, secret: &[u8], pepper: Option<&Pepper>) -> SecretResult<bool> {
+        match self {
+            SecretHash::Phc(secret_hash) => {
+                let secret_hash = secret_hash.password_hash();
+                let config = SecretConfig::try_from(&secret_hash)?;
+                let verified = match config.backend {
+                    Backend::Argon2 => {
+                        let salt = match secret_hash.salt {
+                            Some(salt) => Self::decode_salt(salt)?,
+                            None => Default::default(),
+                        };
+                        let secret = config.prepare(secret, &salt)?;
+                        Self::hasher(pepper, config.algorithm, config.version, config.params)?
+                            .verify_password(&secret, &secret_hash)
+                    }
+                    #[cfg(feature = "scrypt")]
+                    Backend::Scrypt(_) => scrypt::Scrypt.verify_password(secret, &secret_hash),
+                    #[cfg(feature = "pbkdf2")]
+                    Backend::Pbkdf2(_) => pbkdf2::Pbkdf2.verify_password(secret, &secret_hash),
+                    _ => Err(password_hash::Error::Algorithm),
+                };
+                match verified {
+                    Ok(()) => Ok(true),
+                    Err(password_hash::Error::Password) => Ok(false),
+                    Err(err) => Err(SecretError::verification(err)),
+                }
+            }
+            #[cfg(feature = "bcrypt")]
+            SecretHash::Bcrypt(secret_hash) => {
+                bcrypt::verify(secret, secret_hash).map_err(SecretError::Bcrypt)
+            }
+        }
+    }
+
+    pub(crate) fn password_hash(&self) -> Option<PasswordHash<'_>> {
+        match self {
+            SecretHash::Phc(secret_hash) => Some(secret_hash.password_hash()),
+            #[cfg(feature = "bcrypt")]
+            SecretHash::Bcrypt(_) => None,
+        }
+    }
+
+    pub(crate) fn config(&self) -> SecretResult<SecretConfig> {
+        match self {
+            SecretHash::Phc(secret_hash) => SecretConfig::try_from(&secret_hash.password_hash()),
+            #[cfg(feature = "bcrypt")]
+            SecretHash::Bcrypt(secret_hash) => {
+                let parts = secret_hash
+                    .parse::<bcrypt::HashParts>()
+                    .map_err(SecretError::Bcrypt)?;
+                Ok(SecretConfig {
+                    backend: Backend::Bcrypt {
+                        cost: parts.get_cost(),
+                    },
+                    ..Default::default()
+                })
+            }
+        }
+    }
+
+    pub(crate) fn hasher(
+        pepper: Option<&Pepper>,
+        algorithm: Algorithm,
+        version: Version,
+        params: Params,
+    ) -> SecretResult<Argon2<'_>> {
+        match pepper {
+            Some(pepper) => Argon2::new_with_secret(pepper.as_ref(), algorithm, version, params)
+                .map_err(SecretError::InvalidPepper),
+            None => Ok(Argon2::new(algorithm, version, params)),
+        }
+    }
+}
+
+impl SecretHash {
+    // Parameters sorted by name, so encodings differing only in parameter order compare equal.
+    pub(crate) fn canonical(&self) -> Cow<'_, str> {
+        let Some(mut secret_hash) = self.password_hash() else {
+            return Cow::Borrowed(self.as_str());
+        };
+
+        let mut params = secret_hash.params.iter().collect::<Vec<_>>();
+        params.sort_by_key(|(ident, _)| ident.as_str());
+        let mut canonical = ParamsString::new();
+        for (ident, value) in params {
+            if canonical.add_str(ident, value).is_err() {
+                return Cow::Borrowed(self.as_str());
+            }
+        }
+        secret_hash.params = canonical;
+        Cow::Owned(secret_hash.to_string())
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            SecretHash::Phc(secret_hash) => secret_hash.as_str(),
+            #[cfg(feature = "bcrypt")]
+            SecretHash::Bcrypt(secret_hash) => secret_hash,
+        }
+    }
+}
+
+impl Display for SecretHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
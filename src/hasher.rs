@@ -0,0 +1,150 @@
+use std::cell::RefCell;
+
+use argon2::password_hash::{self, Output, ParamsString};
+use argon2::{Argon2, Block, Params, PasswordHash};
+use rand_core::OsRng;
+use zeroize::Zeroize;
+
+use crate::hash::SecretHash;
+use crate::{Backend, Pepper, Secret, SecretConfig, SecretError, SecretResult};
+
+// 64 MiB of 1 KiB blocks, larger buffers are freed after use instead of being kept per thread.
+const MAX_RETAINED_BLOCKS: usize = 64 * 1024;
+
+thread_local! {
+    static MEMORY_BLOCKS: RefCell<Vec<Block>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reuses a per-thread Argon2 memory buffer of up to 64 MiB between calls.
+#[derive(Debug, Clone, Default)]
+pub struct Hasher {
+    config: SecretConfig,
+}
+
+impl Hasher {
+    pub fn new(config: SecretConfig) -> Self {
+        Hasher { config }
+    }
+
+    pub fn config(&self) -> &SecretConfig {
+        &self.config
+    }
+
+    pub fn hash<S: AsRef<[u8]>>(&self, secret: S) -> SecretResult<Secret> {
+        if !matches!(self.config.backend, Backend::Argon2) {
+            return Secret::new_with_config(secret, self.config.clone());
+        }
+        self.config.check_input_len(secret.as_ref())?;
+        self.config.enforce_password_policy(secret.as_ref())?;
+
+        let pepper = self.config.hashing_pepper();
+        let params = self.config.hashing_params()?;
+        let hasher = SecretHash::hasher(
+            pepper,
+            self.config.algorithm,
+            self.config.version,
+            params.clone(),
+        )?;
+        let salt = SecretHash::salt(&self.config, &mut OsRng)?;
+        let mut salt_buffer = [0u8; 64];
+        let salt_bytes = salt
+            .decode_b64(&mut salt_buffer)
+            .map_err(SecretError::Hashing)?;
+        let secret = self.config.prepare(secret.as_ref(), salt_bytes)?;
+        let output = Self::compute(&hasher, &secret, salt_bytes, params.output_len())
+            .map_err(SecretError::Hashing)?;
+
+        let secret_hash = PasswordHash {
+            algorithm: self.config.algorithm.ident(),
+            version: Some(self.config.version.into()),
+            params: ParamsString::try_from(&params).map_err(SecretError::Hashing)?,
+            salt: Some(salt.as_salt()),
+            hash: Some(output),
+        };
+        Ok(Secret {
+            secret: SecretHash::Phc(secret_hash.serialize()),
+            pepper: pepper.cloned(),
+            max_input_len: self.config.max_input_len,
+        })
+    }
+
+    fn verify(
+        &self,
+        secret_hash: &PasswordHash,
+        config: SecretConfig,
+        pepper: Option<&Pepper>,
+        secret: &[u8],
+    ) -> SecretResult<bool> {
+        let (Some(salt), Some(expected)) = (secret_hash.salt, secret_hash.hash) else {
+            return Err(SecretError::CorruptHash(
+                password_hash::Error::PhcStringField,
+            ));
+        };
+
+        let mut salt_buffer = [0u8; 64];
+        let salt_bytes = salt
+            .decode_b64(&mut salt_buffer)
+            .map_err(SecretError::verification)?;
+        let secret = config.prepare(secret, salt_bytes)?;
+        let hasher = SecretHash::hasher(pepper, config.algorithm, config.version, config.params)?;
+        let output = Self::compute(&hasher, &secret, salt_bytes, Some(expected.len()))
+            .map_err(SecretError::verification)?;
+
+        Ok(output == expected)
+    }
+
+    fn compute(
+        hasher: &Argon2,
+        secret: &[u8],
+        salt: &[u8],
+        output_len: Option<usize>,
+    ) -> password_hash::Result<Output> {
+        let output_len = output_len.unwrap_or(Params::DEFAULT_OUTPUT_LEN);
+        Output::init_with(output_len, |out| {
+            MEMORY_BLOCKS.with(|blocks| {
+                let mut blocks = blocks.borrow_mut();
+                let block_count = hasher.params().block_count();
+                if blocks.len() < block_count {
+                    blocks.resize(block_count, Block::default());
+                }
+
+                let result = hasher.hash_password_into_with_memory(
+                    secret,
+                    salt,
+                    out,
+                    &mut blocks[..block_count],
+                );
+                blocks[..block_count].iter_mut().for_each(Zeroize::zeroize);
+                if blocks.len() > MAX_RETAINED_BLOCKS {
+                    *blocks = Vec::new();
+                }
+                Ok(result?)
+            })
+        })
+    }
+}
+
+impl Secret {
+    pub fn verify_with<S: AsRef<[u8]>>(&self, hasher: &Hasher, secret: S) -> bool {
+        self.try_verify_with(hasher, secret).unwrap_or(false)
+    }
+
+    fn try_verify_with<S: AsRef<[u8]>>(&self, hasher: &Hasher, secret: S) -> SecretResult<bool> {
+        self.check_input_len(secret.as_ref())?;
+        hasher.config.check_input_len(secret.as_ref())?;
+        let Some(secret_hash) = self.secret.password_hash() else {
+            return Ok(self.verify(secret));
+        };
+
+        let config = SecretConfig::try_from(&secret_hash)?;
+        if !matches!(config.backend, Backend::Argon2) {
+            return Ok(self.verify(secret));
+        }
+        hasher.verify(
+            &secret_hash,
+            config,
+            self.verifying_pepper()?,
+            secret.as_ref(),
+        )
+    }
+}
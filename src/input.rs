@@ -0,0 +1,84 @@
+use std::fmt::{Debug, Formatter};
+
+use zeroize::Zeroize;
+
+pub struct SecretInput {
+    bytes: Vec<u8>,
+    #[cfg(feature = "mlock")]
+    lock: Option<region::LockGuard>,
+}
+
+impl SecretInput {
+    pub fn new<S: AsRef<[u8]>>(secret: S) -> Self {
+        Self::from(secret.as_ref().to_vec())
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    #[cfg(feature = "mlock")]
+    pub fn is_locked(&self) -> bool {
+        self.lock.is_some()
+    }
+}
+
+impl From<Vec<u8>> for SecretInput {
+    fn from(bytes: Vec<u8>) -> Self {
+        // Locking is best effort, it fails when RLIMIT_MEMLOCK is exhausted.
+        #[cfg(feature = "mlock")]
+        let lock = if bytes.is_empty() {
+            None
+        } else {
+            region::lock(bytes.as_ptr(), bytes.len()).ok()
+        };
+
+        SecretInput {
+            bytes,
+            #[cfg(feature = "mlock")]
+            lock,
+        }
+    }
+}
+
+impl From<String> for SecretInput {
+    fn from(secret: String) -> Self {
+        Self::from(secret.into_bytes())
+    }
+}
+
+impl From<&str> for SecretInput {
+    fn from(secret: &str) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl AsRef<[u8]> for SecretInput {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Zeroize for SecretInput {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Drop for SecretInput {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+        #[cfg(feature = "mlock")]
+        self.lock.take();
+    }
+}
+
+impl Debug for SecretInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretInput(..)")
+    }
+}
@@ -0,0 +1,39 @@
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::{Secret, SecretConfig, SecretResult};
+
+impl Secret {
+    pub fn migrate<L: AsRef<str>, S: AsRef<[u8]>>(
+        legacy: L,
+        secret: S,
+        config: SecretConfig,
+    ) -> SecretResult<Option<Self>> {
+        let legacy = legacy.as_ref();
+        let verified = match Self::decode_sha256_hex(legacy) {
+            Some(expected) => Sha256::digest(secret.as_ref())
+                .as_slice()
+                .ct_eq(&expected)
+                .into(),
+            None => Self::load(legacy)?.try_verify(secret.as_ref())?.is_match(),
+        };
+
+        if !verified {
+            return Ok(None);
+        }
+        Self::hash_secret(secret.as_ref(), config).map(Some)
+    }
+
+    fn decode_sha256_hex(legacy: &str) -> Option<[u8; 32]> {
+        if legacy.len() != 64 || !legacy.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let mut digest = [0u8; 32];
+        for (byte, chunk) in digest.iter_mut().zip(legacy.as_bytes().chunks(2)) {
+            let chunk = std::str::from_utf8(chunk).ok()?;
+            *byte = u8::from_str_radix(chunk, 16).ok()?;
+        }
+        Some(digest)
+    }
+}
@@ -2,32 +2,72 @@
 
 extern crate core;
 
+#[cfg(feature = "async")]
+mod asynchronous;
 #[cfg(feature = "base64")]
 mod base64;
+#[cfg(feature = "breach")]
+mod breach;
+mod calibrate;
 mod config;
+#[cfg(feature = "deterministic")]
+mod deterministic;
 mod display;
+mod env;
 mod eq;
 mod error;
+mod hash;
+mod hasher;
+mod input;
+#[cfg(feature = "legacy")]
+mod legacy;
+mod load_policy;
+mod normalization;
 #[cfg(feature = "openapi")]
 mod openapi;
 mod parse;
+mod password_policy;
+mod payload;
+mod pepper;
+mod pool;
+mod prehash;
+mod preset;
 mod secret;
 #[cfg(feature = "serde")]
 mod serde;
 #[cfg(test)]
 mod tests;
+mod upgrade;
+mod verification;
 
-use argon2::{password_hash::PasswordHashString, Argon2};
+use hash::SecretHash;
 #[cfg(test)]
+#[allow(clippy::single_component_path_imports)]
 use rstest_reuse;
 
-pub use config::{Algorithm, Params, SecretConfig, Version};
+#[cfg(feature = "breach")]
+pub use breach::{BloomFilter, BreachCorpus};
+pub use config::{Algorithm, Backend, Params, SecretConfig, Version};
 pub use error::SecretError;
+pub use hasher::Hasher;
+pub use input::SecretInput;
+pub use load_policy::LoadPolicy;
+pub use normalization::Normalization;
+pub use password_policy::{PasswordPolicy, PasswordViolation};
+pub use payload::{PlainSecret, StoredSecret};
+pub use pepper::{Keyring, Pepper};
+pub use pool::SecretPool;
+pub use prehash::PreHash;
+pub use preset::Preset;
+pub use subtle::{Choice, ConstantTimeEq};
+pub use upgrade::Upgrade;
+pub use verification::Verification;
 
 pub type SecretResult<T> = Result<T, SecretError>;
 
 #[derive(Clone)]
 pub struct Secret {
-    hasher: Argon2<'static>,
-    secret: PasswordHashString,
+    secret: SecretHash,
+    pepper: Option<Pepper>,
+    max_input_len: Option<usize>,
 }
@@ -0,0 +1,80 @@
+use std::sync::OnceLock;
+
+use crate::hash::SecretHash;
+use crate::{Algorithm, Backend, Params, SecretError, SecretResult, Version};
+
+static INSTALLED: OnceLock<LoadPolicy> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct LoadPolicy {
+    pub algorithm: Algorithm,
+    pub version: Version,
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub salt_len: usize,
+    pub output_len: usize,
+    pub allow_other_backends: bool,
+}
+
+impl Default for LoadPolicy {
+    fn default() -> Self {
+        LoadPolicy {
+            algorithm: Algorithm::Argon2d,
+            version: Version::V0x10,
+            m_cost: Params::MIN_M_COST,
+            t_cost: Params::MIN_T_COST,
+            p_cost: Params::MIN_P_COST,
+            salt_len: 0,
+            output_len: Params::MIN_OUTPUT_LEN,
+            allow_other_backends: true,
+        }
+    }
+}
+
+impl LoadPolicy {
+    pub fn install(self) -> SecretResult<()> {
+        INSTALLED
+            .set(self)
+            .map_err(|_| SecretError::LoadPolicyAlreadyInstalled)
+    }
+
+    pub fn installed() -> Option<&'static LoadPolicy> {
+        INSTALLED.get()
+    }
+
+    pub(crate) fn enforce(&self, secret: &SecretHash) -> SecretResult<()> {
+        let config = secret.config()?;
+        if !matches!(config.backend, Backend::Argon2) {
+            return Self::check(self.allow_other_backends, "backend");
+        }
+
+        Self::check(config.algorithm >= self.algorithm, "algorithm")?;
+        Self::check(config.version >= self.version, "version")?;
+        Self::check(config.params.m_cost() >= self.m_cost, "m_cost")?;
+        Self::check(config.params.t_cost() >= self.t_cost, "t_cost")?;
+        Self::check(config.params.p_cost() >= self.p_cost, "p_cost")?;
+
+        let Some(secret_hash) = secret.password_hash() else {
+            return Ok(());
+        };
+        let salt_len = match secret_hash.salt {
+            Some(salt) => salt
+                .decode_b64(&mut [0u8; 64])
+                .map_err(SecretError::CorruptHash)?
+                .len(),
+            None => 0,
+        };
+        let output_len = secret_hash.hash.map(|hash| hash.len()).unwrap_or(0);
+        Self::check(salt_len >= self.salt_len, "salt_len")?;
+        Self::check(output_len >= self.output_len, "output_len")
+    }
+
+    fn check(satisfied: bool, field: &'static str) -> SecretResult<()> {
+        if satisfied {
+            Ok(())
+        } else {
+            Err(SecretError::LoadPolicy(field))
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::ops::Deref;
+
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use crate::{SecretError, SecretResult};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    #[default]
+    None,
+    Nfkc,
+    // RFC 8265 OpaqueString: non-ASCII spaces mapped to U+0020, then NFC.
+    OpaqueString,
+}
+
+pub(crate) enum Prepared<'a> {
+    Raw(&'a [u8]),
+    Owned(Zeroizing<Vec<u8>>),
+}
+
+impl Normalization {
+    const NFKC: &'static [u8] = b"nfkc";
+    const OPAQUE_STRING: &'static [u8] = b"opaque";
+
+    #[cfg(feature = "serde")]
+    pub(crate) const NAMES: &'static str = "none, nfkc, opaque-string";
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Normalization::None => "none",
+            Normalization::Nfkc => "nfkc",
+            Normalization::OpaqueString => "opaque-string",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Normalization::None),
+            "nfkc" => Some(Normalization::Nfkc),
+            "opaque-string" => Some(Normalization::OpaqueString),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn marker(self) -> Option<&'static [u8]> {
+        match self {
+            Normalization::None => None,
+            Normalization::Nfkc => Some(Self::NFKC),
+            Normalization::OpaqueString => Some(Self::OPAQUE_STRING),
+        }
+    }
+
+    pub(crate) fn from_marker(data: &[u8]) -> Self {
+        data.split(|b| *b == b',')
+            .find_map(|token| match token {
+                Self::NFKC => Some(Normalization::Nfkc),
+                Self::OPAQUE_STRING => Some(Normalization::OpaqueString),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn prepare(self, secret: &[u8]) -> SecretResult<Prepared<'_>> {
+        let Ok(secret) = std::str::from_utf8(secret) else {
+            return Ok(Prepared::Raw(secret));
+        };
+
+        match self {
+            Normalization::None => Ok(Prepared::Raw(secret.as_bytes())),
+            Normalization::Nfkc => Ok(Prepared::Owned(Self::collect(|| secret.nfkc()))),
+            Normalization::OpaqueString => {
+                if secret.is_empty() {
+                    return Err(SecretError::Normalization("empty secret"));
+                }
+                if secret.chars().any(char::is_control) {
+                    return Err(SecretError::Normalization("control character"));
+                }
+                Ok(Prepared::Owned(Self::collect(|| {
+                    secret.chars().map(Self::map_space).nfc()
+                })))
+            }
+        }
+    }
+
+    // Sized upfront, so no reallocation leaves a partial copy of the secret behind.
+    fn collect<I: Iterator<Item = char>>(chars: impl Fn() -> I) -> Zeroizing<Vec<u8>> {
+        let len = chars().map(char::len_utf8).sum();
+        let mut bytes = Zeroizing::new(Vec::with_capacity(len));
+        for c in chars() {
+            bytes.extend_from_slice(c.encode_utf8(&mut [0u8; 4]).as_bytes());
+        }
+        bytes
+    }
+
+    fn map_space(c: char) -> char {
+        match c {
+            '\u{00A0}'
+            | '\u{1680}'
+            | '\u{2000}'..='\u{200A}'
+            | '\u{202F}'
+            | '\u{205F}'
+            | '\u{3000}' => ' ',
+            c => c,
+        }
+    }
+}
+
+impl Deref for Prepared<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Prepared::Raw(secret) => secret,
+            Prepared::Owned(secret) => secret,
+        }
+    }
+}
@@ -1,12 +1,16 @@
-use poem_openapi::registry::MetaSchemaRef;
+use poem_openapi::registry::{MetaSchema, MetaSchemaRef};
 use poem_openapi::types::{ParseError, ParseFromJSON, ToJSON, Type};
 use serde_json::{Map, Value};
 use std::borrow::Cow;
+use zeroize::Zeroize;
 
-use crate::Secret;
+use crate::{PlainSecret, Secret, SecretConfig, SecretInput, SecretResult, StoredSecret};
 
 trait AsJson {
     fn as_json(&self) -> String;
+    fn from_json(s: String, config: &SecretConfig) -> SecretResult<Self>
+    where
+        Self: Sized;
 }
 
 #[cfg(not(feature = "base64"))]
@@ -14,6 +18,10 @@ impl AsJson for Secret {
     fn as_json(&self) -> String {
         self.to_string()
     }
+
+    fn from_json(s: String, config: &SecretConfig) -> SecretResult<Self> {
+        Self::load_with_config(s, config)
+    }
 }
 
 #[cfg(feature = "base64")]
@@ -21,6 +29,10 @@ impl AsJson for Secret {
     fn as_json(&self) -> String {
         self.to_base64()
     }
+
+    fn from_json(s: String, config: &SecretConfig) -> SecretResult<Self> {
+        Self::load_from_base64_with_config(s, config)
+    }
 }
 
 impl Type for Secret {
@@ -80,15 +92,13 @@ impl Type for Secret {
 
 impl ParseFromJSON for Secret {
     fn parse_from_json(value: Option<Value>) -> Result<Self, ParseError<Self>> {
-        let value = value.unwrap_or_default();
-
-        match value {
+        match value.unwrap_or_default() {
             Value::Object(mut obj) => {
-                let secret: String = ParseFromJSON::parse_from_json(obj.remove("secret"))
+                let secret: SecretInput = ParseFromJSON::parse_from_json(obj.remove("secret"))
                     .map_err(ParseError::propagate)?;
-                Self::wrap(secret).map_err(ParseError::custom)
+                Self::new(secret).map_err(ParseError::custom)
             }
-            _ => Err(ParseError::expected_type(value)),
+            value => Err(unexpected_type(value)),
         }
     }
 }
@@ -102,3 +112,140 @@ impl ToJSON for Secret {
         Some(Value::Object(object))
     }
 }
+
+impl Type for SecretInput {
+    const IS_REQUIRED: bool = true;
+    type RawValueType = Self;
+    type RawElementValueType = Self;
+
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("string(password)")
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(Box::new(MetaSchema {
+            write_only: true,
+            ..MetaSchema::new_with_format("string", "password")
+        }))
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+
+    fn raw_element_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+        Box::new(std::iter::IntoIterator::into_iter(self.as_raw_value()))
+    }
+}
+
+impl ParseFromJSON for SecretInput {
+    fn parse_from_json(value: Option<Value>) -> Result<Self, ParseError<Self>> {
+        match value.unwrap_or_default() {
+            Value::String(secret) => Ok(SecretInput::from(secret)),
+            value => Err(unexpected_type(value)),
+        }
+    }
+}
+
+impl Type for PlainSecret {
+    const IS_REQUIRED: bool = true;
+    type RawValueType = Self;
+    type RawElementValueType = Self;
+
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("PlainSecret")
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        SecretInput::schema_ref()
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+
+    fn raw_element_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+        Box::new(std::iter::IntoIterator::into_iter(self.as_raw_value()))
+    }
+}
+
+impl ParseFromJSON for PlainSecret {
+    fn parse_from_json(value: Option<Value>) -> Result<Self, ParseError<Self>> {
+        let secret = SecretInput::parse_from_json(value).map_err(ParseError::propagate)?;
+        Self::new(secret).map_err(ParseError::custom)
+    }
+}
+
+impl Type for StoredSecret {
+    const IS_REQUIRED: bool = true;
+    type RawValueType = Self;
+    type RawElementValueType = Self;
+
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("StoredSecret")
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(Box::new(MetaSchema {
+            description: Some("Hashed secret, safe to store and round-trip"),
+            ..MetaSchema::new("string")
+        }))
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+
+    fn raw_element_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+        Box::new(std::iter::IntoIterator::into_iter(self.as_raw_value()))
+    }
+}
+
+impl ParseFromJSON for StoredSecret {
+    fn parse_from_json(value: Option<Value>) -> Result<Self, ParseError<Self>> {
+        match value.unwrap_or_default() {
+            Value::String(secretized) => Secret::from_json(secretized, SecretConfig::payload())
+                .map(StoredSecret::from)
+                .map_err(ParseError::custom),
+            value => Err(ParseError::expected_type(value)),
+        }
+    }
+}
+
+impl ToJSON for StoredSecret {
+    fn to_json(&self) -> Option<Value> {
+        Some(Value::String(self.as_ref().as_json()))
+    }
+}
+
+// Unlike `ParseError::expected_type`, the rejected value is wiped instead of echoed back.
+fn unexpected_type<T: Type>(mut value: Value) -> ParseError<T> {
+    let found = match &value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+    zeroize_value(&mut value);
+    ParseError::custom(format!(
+        r#"Expected input type "{}", found {found}."#,
+        T::name()
+    ))
+}
+
+pub(crate) fn zeroize_value(value: &mut Value) {
+    match value {
+        Value::String(string) => string.zeroize(),
+        Value::Array(values) => values.iter_mut().for_each(zeroize_value),
+        Value::Object(map) => map.values_mut().for_each(zeroize_value),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
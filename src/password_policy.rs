@@ -0,0 +1,171 @@
+use std::sync::OnceLock;
+
+use crate::{SecretError, SecretResult};
+
+static INSTALLED: OnceLock<PasswordPolicy> = OnceLock::new();
+
+#[derive(Debug, Default, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub max_bytes: Option<usize>,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub banned: Vec<String>,
+    #[cfg(feature = "entropy")]
+    pub min_entropy: Option<u32>,
+    #[cfg(feature = "breach")]
+    pub breached: Option<crate::BreachCorpus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PasswordViolation {
+    #[error("longer than {0} bytes")]
+    TooManyBytes(usize),
+    #[error("not valid UTF-8")]
+    InvalidUtf8,
+    #[error("shorter than {0} characters")]
+    TooShort(usize),
+    #[error("longer than {0} characters")]
+    TooLong(usize),
+    #[error("missing a lowercase letter")]
+    MissingLowercase,
+    #[error("missing an uppercase letter")]
+    MissingUppercase,
+    #[error("missing a digit")]
+    MissingDigit,
+    #[error("missing a symbol")]
+    MissingSymbol,
+    #[error("banned password")]
+    Banned,
+    #[cfg(feature = "breach")]
+    #[error("password appears in a known breach")]
+    Breached,
+    #[cfg(feature = "entropy")]
+    #[error("estimated entropy is below {0} bits")]
+    TooWeak(u32),
+}
+
+impl PasswordPolicy {
+    pub fn install(self) -> SecretResult<()> {
+        INSTALLED
+            .set(self)
+            .map_err(|_| SecretError::PasswordPolicyAlreadyInstalled)
+    }
+
+    pub fn installed() -> Option<&'static PasswordPolicy> {
+        INSTALLED.get()
+    }
+
+    pub fn validate(&self, secret: &[u8]) -> Result<(), PasswordViolation> {
+        // Checked before decoding so oversized inputs are rejected without further work.
+        if let Some(max_bytes) = self.max_bytes {
+            Self::check(
+                secret.len() <= max_bytes,
+                PasswordViolation::TooManyBytes(max_bytes),
+            )?;
+        }
+        let password = std::str::from_utf8(secret).map_err(|_| PasswordViolation::InvalidUtf8)?;
+
+        let length = password.chars().count();
+        Self::check(
+            length >= self.min_length,
+            PasswordViolation::TooShort(self.min_length),
+        )?;
+        if let Some(max_length) = self.max_length {
+            Self::check(length <= max_length, PasswordViolation::TooLong(max_length))?;
+        }
+
+        Self::check(
+            !self.require_lowercase || password.chars().any(char::is_lowercase),
+            PasswordViolation::MissingLowercase,
+        )?;
+        Self::check(
+            !self.require_uppercase || password.chars().any(char::is_uppercase),
+            PasswordViolation::MissingUppercase,
+        )?;
+        Self::check(
+            !self.require_digit || password.chars().any(|c| c.is_ascii_digit()),
+            PasswordViolation::MissingDigit,
+        )?;
+        Self::check(
+            !self.require_symbol || password.chars().any(|c| !c.is_alphanumeric()),
+            PasswordViolation::MissingSymbol,
+        )?;
+        Self::check(
+            !self.banned.iter().any(|banned| {
+                banned
+                    .chars()
+                    .flat_map(char::to_lowercase)
+                    .eq(password.chars().flat_map(char::to_lowercase))
+            }),
+            PasswordViolation::Banned,
+        )?;
+
+        #[cfg(feature = "breach")]
+        if let Some(breached) = &self.breached {
+            Self::check(!breached.contains(secret), PasswordViolation::Breached)?;
+        }
+
+        #[cfg(feature = "entropy")]
+        if let Some(min_entropy) = self.min_entropy {
+            Self::check(
+                Self::estimate_entropy(password) >= min_entropy,
+                PasswordViolation::TooWeak(min_entropy),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Charset-size estimate where repeated and sequential characters only count for one bit each.
+    #[cfg(feature = "entropy")]
+    pub fn estimate_entropy(password: &str) -> u32 {
+        let mut pool = 0u32;
+        if password.chars().any(|c| c.is_ascii_lowercase()) {
+            pool += 26;
+        }
+        if password.chars().any(|c| c.is_ascii_uppercase()) {
+            pool += 26;
+        }
+        if password.chars().any(|c| c.is_ascii_digit()) {
+            pool += 10;
+        }
+        if password
+            .chars()
+            .any(|c| c.is_ascii_punctuation() || c == ' ')
+        {
+            pool += 33;
+        }
+        if !password.is_ascii() {
+            pool += 100;
+        }
+        let bits_per_char = f64::from(pool.max(1)).log2();
+
+        let mut entropy = 0f64;
+        let mut previous: Option<char> = None;
+        for c in password.chars() {
+            let predictable = previous.is_some_and(|p| {
+                let (p, c) = (u32::from(p), u32::from(c));
+                c == p || c == p + 1 || c + 1 == p
+            });
+            entropy += if predictable { 1.0 } else { bits_per_char };
+            previous = Some(c);
+        }
+        entropy as u32
+    }
+
+    pub(crate) fn enforce(&self, secret: &[u8]) -> SecretResult<()> {
+        self.validate(secret).map_err(SecretError::PasswordPolicy)
+    }
+
+    fn check(satisfied: bool, violation: PasswordViolation) -> Result<(), PasswordViolation> {
+        if satisfied {
+            Ok(())
+        } else {
+            Err(violation)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::OnceLock;
+
+use crate::{Secret, SecretConfig, SecretError, SecretResult};
+
+static INSTALLED: OnceLock<SecretConfig> = OnceLock::new();
+
+#[derive(Debug)]
+pub struct PlainSecret(Secret);
+
+#[derive(Debug, Clone)]
+pub struct StoredSecret(Secret);
+
+impl PlainSecret {
+    pub fn new<S: AsRef<[u8]>>(secret: S) -> SecretResult<Self> {
+        Self::new_with_config(secret, SecretConfig::payload().clone())
+    }
+
+    pub fn new_with_config<S: AsRef<[u8]>>(secret: S, config: SecretConfig) -> SecretResult<Self> {
+        Secret::new_with_config(secret, config).map(PlainSecret)
+    }
+
+    pub fn into_secret(self) -> Secret {
+        self.0
+    }
+}
+
+impl StoredSecret {
+    pub fn load<S: AsRef<str>>(secretized: S) -> SecretResult<Self> {
+        Self::load_with_config(secretized, SecretConfig::payload())
+    }
+
+    pub fn load_with_config<S: AsRef<str>>(
+        secretized: S,
+        config: &SecretConfig,
+    ) -> SecretResult<Self> {
+        Secret::load_with_config(secretized, config).map(StoredSecret)
+    }
+
+    /// Attaches the pepper for the hash's key id, see `Secret::with_config`.
+    pub fn with_config(self, config: &SecretConfig) -> SecretResult<Self> {
+        self.0.with_config(config).map(StoredSecret)
+    }
+
+    pub fn into_secret(self) -> Secret {
+        self.0
+    }
+}
+
+impl SecretConfig {
+    /// Used by `PlainSecret` and `StoredSecret` instead of the default, also when parsed.
+    pub fn install_payload(self) -> SecretResult<()> {
+        INSTALLED
+            .set(self)
+            .map_err(|_| SecretError::PayloadConfigAlreadyInstalled)
+    }
+
+    pub fn installed_payload() -> Option<&'static SecretConfig> {
+        INSTALLED.get()
+    }
+
+    pub(crate) fn payload() -> &'static SecretConfig {
+        static DEFAULT: OnceLock<SecretConfig> = OnceLock::new();
+        INSTALLED
+            .get()
+            .unwrap_or_else(|| DEFAULT.get_or_init(SecretConfig::default))
+    }
+}
+
+impl AsRef<Secret> for PlainSecret {
+    fn as_ref(&self) -> &Secret {
+        &self.0
+    }
+}
+
+impl AsRef<Secret> for StoredSecret {
+    fn as_ref(&self) -> &Secret {
+        &self.0
+    }
+}
+
+impl From<PlainSecret> for StoredSecret {
+    fn from(secret: PlainSecret) -> Self {
+        StoredSecret(secret.0)
+    }
+}
+
+impl From<Secret> for StoredSecret {
+    fn from(secret: Secret) -> Self {
+        StoredSecret(secret)
+    }
+}
+
+impl From<StoredSecret> for Secret {
+    fn from(secret: StoredSecret) -> Self {
+        secret.0
+    }
+}
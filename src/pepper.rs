@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use argon2::KeyId;
+use zeroize::Zeroizing;
+
+use crate::{SecretError, SecretResult};
+
+#[derive(Clone)]
+pub struct Pepper(Arc<Zeroizing<Vec<u8>>>);
+
+impl Pepper {
+    pub fn new<S: AsRef<[u8]>>(pepper: S) -> Self {
+        Self::from(pepper.as_ref().to_vec())
+    }
+}
+
+impl From<Vec<u8>> for Pepper {
+    fn from(pepper: Vec<u8>) -> Self {
+        Pepper(Arc::new(Zeroizing::new(pepper)))
+    }
+}
+
+impl AsRef<[u8]> for Pepper {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl Debug for Pepper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pepper(..)")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keyring {
+    current: KeyId,
+    peppers: HashMap<KeyId, Pepper>,
+}
+
+impl Keyring {
+    pub fn new<K: AsRef<[u8]>>(key_id: K, pepper: Pepper) -> SecretResult<Self> {
+        let current = Self::key_id(key_id)?;
+        Ok(Keyring {
+            current,
+            peppers: HashMap::from([(current, pepper)]),
+        })
+    }
+
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key_id: K, pepper: Pepper) -> SecretResult<()> {
+        self.peppers.insert(Self::key_id(key_id)?, pepper);
+        Ok(())
+    }
+
+    pub fn rotate<K: AsRef<[u8]>>(&mut self, key_id: K, pepper: Pepper) -> SecretResult<()> {
+        let key_id = Self::key_id(key_id)?;
+        self.peppers.insert(key_id, pepper);
+        self.current = key_id;
+        Ok(())
+    }
+
+    pub fn current(&self) -> &[u8] {
+        self.current.as_bytes()
+    }
+
+    pub(crate) fn current_key_id(&self) -> KeyId {
+        self.current
+    }
+
+    pub(crate) fn current_pepper(&self) -> &Pepper {
+        &self.peppers[&self.current]
+    }
+
+    pub(crate) fn get(&self, key_id: &[u8]) -> Option<&Pepper> {
+        KeyId::new(key_id)
+            .ok()
+            .and_then(|key_id| self.peppers.get(&key_id))
+    }
+
+    fn key_id<K: AsRef<[u8]>>(key_id: K) -> SecretResult<KeyId> {
+        KeyId::new(key_id.as_ref()).map_err(SecretError::InvalidKeyId)
+    }
+}
@@ -0,0 +1,228 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::{Secret, SecretConfig, SecretError, SecretResult, Verification};
+
+#[derive(Clone)]
+pub struct SecretPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    config: SecretConfig,
+    max_concurrency: usize,
+    state: Mutex<PoolState>,
+    released: Condvar,
+    // Shared by sync and async callers, so async waiters never hold a blocking thread.
+    #[cfg(feature = "async")]
+    permits: tokio::sync::Semaphore,
+}
+
+#[derive(Default)]
+struct PoolState {
+    in_flight: usize,
+    queued: usize,
+}
+
+struct Permit {
+    pool: Arc<PoolInner>,
+}
+
+#[cfg(feature = "async")]
+struct Queued<'a> {
+    pool: &'a PoolInner,
+}
+
+impl SecretPool {
+    pub fn new(config: SecretConfig, max_concurrency: usize) -> Self {
+        let max_concurrency = max_concurrency.max(1);
+        SecretPool {
+            inner: Arc::new(PoolInner {
+                config,
+                max_concurrency,
+                state: Mutex::default(),
+                released: Condvar::new(),
+                #[cfg(feature = "async")]
+                permits: tokio::sync::Semaphore::new(max_concurrency),
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &SecretConfig {
+        &self.inner.config
+    }
+
+    pub fn max_concurrency(&self) -> usize {
+        self.inner.max_concurrency
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.inner.state().in_flight
+    }
+
+    pub fn queue_depth(&self) -> usize {
+        self.inner.state().queued
+    }
+
+    pub fn hash<S: AsRef<[u8]>>(&self, secret: S) -> SecretResult<Secret> {
+        let _permit = PoolInner::acquire(&self.inner);
+        Secret::new_with_config(secret, self.inner.config.clone())
+    }
+
+    pub fn try_hash<S: AsRef<[u8]>>(&self, secret: S) -> SecretResult<Secret> {
+        let _permit = PoolInner::try_acquire(&self.inner)?;
+        Secret::new_with_config(secret, self.inner.config.clone())
+    }
+
+    pub fn verify<S: AsRef<[u8]>>(&self, secret: &Secret, input: S) -> SecretResult<Verification> {
+        let _permit = PoolInner::acquire(&self.inner);
+        secret.try_verify_with_config(input, &self.inner.config)
+    }
+
+    pub fn try_verify<S: AsRef<[u8]>>(
+        &self,
+        secret: &Secret,
+        input: S,
+    ) -> SecretResult<Verification> {
+        let _permit = PoolInner::try_acquire(&self.inner)?;
+        secret.try_verify_with_config(input, &self.inner.config)
+    }
+
+    /// Waits for a pool permit without blocking, then for the global async concurrency limit.
+    #[cfg(feature = "async")]
+    pub async fn hash_async<S: AsRef<[u8]> + Send + 'static>(
+        &self,
+        secret: S,
+    ) -> SecretResult<Secret> {
+        let permit = PoolInner::acquire_async(&self.inner).await;
+        Secret::spawn_blocking(move || Secret::new_with_config(secret, permit.pool.config.clone()))
+            .await?
+    }
+
+    /// Fails with `PoolExhausted` instead of waiting, for load shedding.
+    #[cfg(feature = "async")]
+    pub async fn try_hash_async<S: AsRef<[u8]> + Send + 'static>(
+        &self,
+        secret: S,
+    ) -> SecretResult<Secret> {
+        let permit = PoolInner::try_acquire(&self.inner)?;
+        Secret::try_spawn_blocking(move || {
+            Secret::new_with_config(secret, permit.pool.config.clone())
+        })
+        .await?
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn verify_async<S: AsRef<[u8]> + Send + 'static>(
+        &self,
+        secret: &Secret,
+        input: S,
+    ) -> SecretResult<Verification> {
+        let permit = PoolInner::acquire_async(&self.inner).await;
+        let secret = secret.clone();
+        Secret::spawn_blocking(move || secret.try_verify_with_config(input, &permit.pool.config))
+            .await?
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn try_verify_async<S: AsRef<[u8]> + Send + 'static>(
+        &self,
+        secret: &Secret,
+        input: S,
+    ) -> SecretResult<Verification> {
+        let permit = PoolInner::try_acquire(&self.inner)?;
+        let secret = secret.clone();
+        Secret::try_spawn_blocking(move || {
+            secret.try_verify_with_config(input, &permit.pool.config)
+        })
+        .await?
+    }
+}
+
+impl PoolInner {
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn acquire(pool: &Arc<Self>) -> Permit {
+        let mut state = pool.state();
+        if !pool.claim(&state) {
+            state.queued += 1;
+            while !pool.claim(&state) {
+                state = pool
+                    .released
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+            state.queued -= 1;
+        }
+        state.in_flight += 1;
+
+        Permit { pool: pool.clone() }
+    }
+
+    fn try_acquire(pool: &Arc<Self>) -> SecretResult<Permit> {
+        let mut state = pool.state();
+        if !pool.claim(&state) {
+            return Err(SecretError::PoolExhausted);
+        }
+        state.in_flight += 1;
+
+        Ok(Permit { pool: pool.clone() })
+    }
+
+    #[cfg(feature = "async")]
+    async fn acquire_async(pool: &Arc<Self>) -> Permit {
+        if let Ok(permit) = Self::try_acquire(pool) {
+            return permit;
+        }
+
+        let queued = Queued::new(pool);
+        pool.permits
+            .acquire()
+            .await
+            .expect("pool permits are never closed")
+            .forget();
+        drop(queued);
+        pool.state().in_flight += 1;
+
+        Permit { pool: pool.clone() }
+    }
+
+    #[cfg(feature = "async")]
+    fn claim(&self, _state: &PoolState) -> bool {
+        self.permits
+            .try_acquire()
+            .map(tokio::sync::SemaphorePermit::forget)
+            .is_ok()
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn claim(&self, state: &PoolState) -> bool {
+        state.in_flight < self.max_concurrency
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self.pool.state();
+        state.in_flight -= 1;
+        #[cfg(feature = "async")]
+        self.pool.permits.add_permits(1);
+        self.pool.released.notify_one();
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a> Queued<'a> {
+    fn new(pool: &'a PoolInner) -> Self {
+        pool.state().queued += 1;
+        Queued { pool }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        self.pool.state().queued -= 1;
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(feature = "prehash")]
+use hmac::{Hmac, Mac};
+#[cfg(feature = "prehash")]
+use sha2::Sha512;
+#[cfg(feature = "prehash")]
+use zeroize::Zeroizing;
+
+use crate::normalization::Prepared;
+use crate::SecretResult;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PreHash {
+    #[default]
+    None,
+    // Keyed with the salt, so equal inputs still compact to distinct Argon2 passwords.
+    #[cfg(feature = "prehash")]
+    HmacSha512,
+}
+
+impl PreHash {
+    const HMAC_SHA512: &'static [u8] = b"hmac-sha512";
+
+    #[cfg(feature = "serde")]
+    pub(crate) const NAMES: &'static str = if cfg!(feature = "prehash") {
+        "none, hmac-sha512"
+    } else {
+        "none"
+    };
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            PreHash::None => "none",
+            #[cfg(feature = "prehash")]
+            PreHash::HmacSha512 => "hmac-sha512",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(PreHash::None),
+            #[cfg(feature = "prehash")]
+            "hmac-sha512" => Some(PreHash::HmacSha512),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn marker(self) -> Option<&'static [u8]> {
+        match self {
+            PreHash::None => None,
+            #[cfg(feature = "prehash")]
+            PreHash::HmacSha512 => Some(Self::HMAC_SHA512),
+        }
+    }
+
+    // Pre-hashed secrets cannot be verified without the feature, so loading them fails instead.
+    pub(crate) fn from_marker(data: &[u8]) -> SecretResult<Self> {
+        let pre_hashed = data
+            .split(|b| *b == b',')
+            .any(|token| token == Self::HMAC_SHA512);
+        match pre_hashed {
+            false => Ok(PreHash::None),
+            #[cfg(feature = "prehash")]
+            true => Ok(PreHash::HmacSha512),
+            #[cfg(not(feature = "prehash"))]
+            true => Err(crate::SecretError::PreHashUnavailable),
+        }
+    }
+
+    pub(crate) fn apply<'a>(
+        self,
+        secret: Prepared<'a>,
+        #[cfg_attr(not(feature = "prehash"), allow(unused_variables))] salt: &[u8],
+    ) -> Prepared<'a> {
+        match self {
+            PreHash::None => secret,
+            #[cfg(feature = "prehash")]
+            PreHash::HmacSha512 => {
+                let mut mac =
+                    Hmac::<Sha512>::new_from_slice(salt).expect("HMAC accepts keys of any length");
+                mac.update(&secret);
+                Prepared::Owned(Zeroizing::new(mac.finalize().into_bytes().to_vec()))
+            }
+        }
+    }
+}
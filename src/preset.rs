@@ -0,0 +1,49 @@
+use crate::{Algorithm, Params, SecretConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    Rfc9106FirstRecommended,
+    Rfc9106SecondRecommended,
+    Owasp46MiB,
+    Owasp19MiB,
+    Owasp12MiB,
+    Owasp9MiB,
+    Owasp7MiB,
+    Testing,
+}
+
+impl Preset {
+    pub const fn params(self) -> Params {
+        let (m_cost, t_cost, p_cost) = match self {
+            Preset::Rfc9106FirstRecommended => (2 * 1024 * 1024, 1, 4),
+            Preset::Rfc9106SecondRecommended => (64 * 1024, 3, 4),
+            Preset::Owasp46MiB => (46 * 1024, 1, 1),
+            Preset::Owasp19MiB => (19 * 1024, 2, 1),
+            Preset::Owasp12MiB => (12 * 1024, 3, 1),
+            Preset::Owasp9MiB => (9 * 1024, 4, 1),
+            Preset::Owasp7MiB => (7 * 1024, 5, 1),
+            Preset::Testing => (Params::MIN_M_COST, Params::MIN_T_COST, Params::MIN_P_COST),
+        };
+
+        match Params::new(m_cost, t_cost, p_cost, None) {
+            Ok(params) => params,
+            Err(_) => panic!("invalid preset parameters"),
+        }
+    }
+}
+
+impl SecretConfig {
+    pub fn preset(preset: Preset) -> Self {
+        SecretConfig {
+            algorithm: Algorithm::Argon2id,
+            params: preset.params(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<Preset> for SecretConfig {
+    fn from(preset: Preset) -> Self {
+        SecretConfig::preset(preset)
+    }
+}
@@ -1,8 +1,9 @@
-use argon2::{password_hash::SaltString, Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::SaltString;
 use rand_core::OsRng;
 use zeroize::Zeroize;
 
-use crate::{Secret, SecretConfig, SecretError, SecretResult};
+use crate::hash::SecretHash;
+use crate::{LoadPolicy, Pepper, Secret, SecretConfig, SecretError, SecretResult};
 
 impl Secret {
     pub fn new<S: AsRef<[u8]>>(secret: S) -> SecretResult<Self> {
@@ -14,6 +15,8 @@ impl Secret {
     }
 
     pub fn new_with_config<S: AsRef<[u8]>>(secret: S, config: SecretConfig) -> SecretResult<Self> {
+        config.check_input_len(secret.as_ref())?;
+        config.enforce_password_policy(secret.as_ref())?;
         Self::hash_secret(secret.as_ref(), config)
     }
 
@@ -21,6 +24,8 @@ impl Secret {
         mut secret: S,
         config: SecretConfig,
     ) -> SecretResult<Self> {
+        config.check_input_len(secret.as_ref())?;
+        config.enforce_password_policy(secret.as_ref())?;
         match Self::hash_secret(secret.as_ref(), config) {
             Ok(sec) => {
                 secret.zeroize();
@@ -31,40 +36,73 @@ impl Secret {
     }
 
     pub fn load<S: AsRef<str>>(secretized: S) -> SecretResult<Self> {
-        let secret_hash =
-            PasswordHash::new(secretized.as_ref()).map_err(SecretError::HashParsing)?;
+        Self::load_with_config(secretized, &SecretConfig::default())
+    }
 
-        let algorithm = argon2::Algorithm::try_from(secret_hash.algorithm)
-            .map_err(SecretError::InvalidAlgorithm)?;
-        let version = match secret_hash.version {
-            Some(ver) => argon2::Version::try_from(ver).map_err(SecretError::InvalidVersion)?,
-            None => argon2::Version::default(),
-        };
-        let params = argon2::Params::try_from(&secret_hash).map_err(SecretError::InvalidParams)?;
+    pub fn load_with_config<S: AsRef<str>>(
+        secretized: S,
+        config: &SecretConfig,
+    ) -> SecretResult<Self> {
+        let secret = SecretHash::load(secretized.as_ref())?;
+        if let Some(policy) = config.load_policy.as_ref().or(LoadPolicy::installed()) {
+            policy.enforce(&secret)?;
+        }
 
-        Ok(Secret {
-            hasher: Argon2::new(algorithm, version, params),
-            secret: secret_hash.serialize(),
-        })
+        Secret {
+            secret,
+            pepper: None,
+            max_input_len: None,
+        }
+        .with_config(config)
+    }
+
+    /// Attaches the pepper for the hash's key id and the input limit, e.g. to deserialized secrets.
+    pub fn with_config(mut self, config: &SecretConfig) -> SecretResult<Self> {
+        let pepper = config.pepper_for(self.secret.config()?.params.keyid())?;
+        self.pepper = pepper.or(self.pepper);
+        self.max_input_len = config.max_input_len.or(self.max_input_len);
+        Ok(self)
     }
 
     pub fn verify<S: AsRef<[u8]>>(&self, secret: S) -> bool {
-        self.hasher
-            .verify_password(secret.as_ref(), &self.secret.password_hash())
-            .is_ok()
+        self.try_verify(secret)
+            .map(|verification| verification.is_match())
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn check_input_len(&self, secret: &[u8]) -> SecretResult<()> {
+        match self.max_input_len {
+            Some(max_input_len) if secret.len() > max_input_len => {
+                Err(SecretError::InputTooLong(max_input_len))
+            }
+            _ => Ok(()),
+        }
     }
 
-    fn hash_secret(secret: &[u8], config: SecretConfig) -> SecretResult<Self> {
-        let hasher = Argon2::new(config.algorithm, config.version, config.params);
-        let salt = SaltString::generate(&mut OsRng);
-        let secret_hash = hasher
-            .hash_password(secret.as_ref(), &salt)
-            .map_err(SecretError::Hashing)?
-            .serialize();
+    pub(crate) fn verifying_pepper(&self) -> SecretResult<Option<&Pepper>> {
+        match &self.pepper {
+            Some(pepper) => Ok(Some(pepper)),
+            None if self.secret.config()?.params.keyid().is_empty() => Ok(None),
+            None => Err(SecretError::UnknownKeyId),
+        }
+    }
 
+    // Skips the password policy, for rehashing secrets that were already verified.
+    pub(crate) fn hash_secret(secret: &[u8], config: SecretConfig) -> SecretResult<Self> {
+        let salt = SecretHash::salt(&config, &mut OsRng)?;
+        Self::hash_secret_with_salt(secret, config, &salt)
+    }
+
+    pub(crate) fn hash_secret_with_salt(
+        secret: &[u8],
+        config: SecretConfig,
+        salt: &SaltString,
+    ) -> SecretResult<Self> {
+        config.check_input_len(secret)?;
         Ok(Secret {
-            hasher,
-            secret: secret_hash,
+            secret: SecretHash::generate(secret, &config, salt)?,
+            pepper: config.hashing_pepper().cloned(),
+            max_input_len: config.max_input_len,
         })
     }
 }
@@ -1,8 +1,14 @@
-use crate::{Secret, SecretResult};
+use serde::de::Error as _;
+use serde::ser::Error as _;
+
+use crate::{
+    Algorithm, Backend, Normalization, Params, PlainSecret, PreHash, Secret, SecretConfig,
+    SecretInput, SecretResult, StoredSecret, Version,
+};
 
 trait SerDeser {
     fn ser(&self) -> String;
-    fn deser(s: String) -> SecretResult<Self>
+    fn deser(s: String, config: &SecretConfig) -> SecretResult<Self>
     where
         Self: Sized;
 }
@@ -13,8 +19,8 @@ impl SerDeser for Secret {
         self.to_string()
     }
 
-    fn deser(s: String) -> SecretResult<Self> {
-        Self::load(s)
+    fn deser(s: String, config: &SecretConfig) -> SecretResult<Self> {
+        Self::load_with_config(s, config)
     }
 }
 
@@ -24,8 +30,8 @@ impl SerDeser for Secret {
         self.to_base64()
     }
 
-    fn deser(s: String) -> SecretResult<Self> {
-        Self::load_from_base64(s)
+    fn deser(s: String, config: &SecretConfig) -> SecretResult<Self> {
+        Self::load_from_base64_with_config(s, config)
     }
 }
 
@@ -43,6 +49,220 @@ impl<'de> serde::Deserialize<'de> for Secret {
     where
         D: serde::Deserializer<'de>,
     {
-        Self::deser(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+        Self::deser(String::deserialize(deserializer)?, &SecretConfig::default())
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SecretInput {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SecretInputVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SecretInputVisitor {
+            type Value = SecretInput;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a secret string or byte sequence")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(SecretInput::new(v))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(SecretInput::from(v))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(SecretInput::new(v))
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(SecretInput::from(v))
+            }
+
+            // The default implementations echo the rejected value back in the error message.
+            fn visit_bool<E: serde::de::Error>(self, _: bool) -> Result<Self::Value, E> {
+                Err(E::custom("invalid type: boolean, expected a secret"))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, _: i64) -> Result<Self::Value, E> {
+                Err(E::custom("invalid type: integer, expected a secret"))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, _: u64) -> Result<Self::Value, E> {
+                Err(E::custom("invalid type: integer, expected a secret"))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, _: f64) -> Result<Self::Value, E> {
+                Err(E::custom("invalid type: floating point, expected a secret"))
+            }
+
+            fn visit_char<E: serde::de::Error>(self, v: char) -> Result<Self::Value, E> {
+                Ok(SecretInput::new(v.encode_utf8(&mut [0u8; 4])))
+            }
+        }
+
+        // Self-describing formats hand the rejected value to the visitor instead of echoing it.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(SecretInputVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(SecretInputVisitor)
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PlainSecret {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::new(SecretInput::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl serde::Serialize for StoredSecret {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for StoredSecret {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Secret::deser(String::deserialize(deserializer)?, SecretConfig::payload())
+            .map(StoredSecret::from)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SecretConfigRepr {
+    algorithm: String,
+    version: u32,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_len: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    salt_len: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    normalization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pre_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_input_len: Option<usize>,
+}
+
+impl Default for SecretConfigRepr {
+    fn default() -> Self {
+        SecretConfigRepr {
+            algorithm: Algorithm::default().to_string(),
+            version: Version::default().into(),
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+            output_len: None,
+            salt_len: None,
+            normalization: None,
+            pre_hash: None,
+            max_input_len: None,
+        }
+    }
+}
+
+impl serde::Serialize for SecretConfig {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if !matches!(self.backend, Backend::Argon2) {
+            return Err(S::Error::custom(
+                "only Argon2 configurations can be serialized",
+            ));
+        }
+
+        SecretConfigRepr {
+            algorithm: self.algorithm.to_string(),
+            version: self.version.into(),
+            memory_kib: self.params.m_cost(),
+            iterations: self.params.t_cost(),
+            parallelism: self.params.p_cost(),
+            output_len: self.params.output_len(),
+            salt_len: self.salt_len,
+            normalization: (self.normalization != Normalization::None)
+                .then(|| self.normalization.name().to_string()),
+            pre_hash: (self.pre_hash != PreHash::None).then(|| self.pre_hash.name().to_string()),
+            max_input_len: self.max_input_len,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SecretConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let repr = SecretConfigRepr::deserialize(deserializer)?;
+        let algorithm = Algorithm::new(&repr.algorithm).map_err(|_| {
+            D::Error::custom(format!(
+                "invalid algorithm `{}`, expected one of argon2d, argon2i, argon2id",
+                repr.algorithm
+            ))
+        })?;
+        let version = Version::try_from(repr.version).map_err(|_| {
+            D::Error::custom(format!(
+                "invalid version `{}`, expected one of 16, 19",
+                repr.version
+            ))
+        })?;
+        let params = Params::new(
+            repr.memory_kib,
+            repr.iterations,
+            repr.parallelism,
+            repr.output_len,
+        )
+        .map_err(|err| D::Error::custom(format!("invalid parameters: {err}")))?;
+
+        let normalization = match repr.normalization.as_deref() {
+            Some(name) => Normalization::from_name(name).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "invalid normalization `{name}`, expected one of {}",
+                    Normalization::NAMES
+                ))
+            })?,
+            None => Normalization::None,
+        };
+        let pre_hash = match repr.pre_hash.as_deref() {
+            Some(name) => PreHash::from_name(name).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "invalid pre_hash `{name}`, expected one of {}",
+                    PreHash::NAMES
+                ))
+            })?,
+            None => PreHash::None,
+        };
+
+        Ok(SecretConfig {
+            algorithm,
+            version,
+            params,
+            salt_len: repr.salt_len,
+            normalization,
+            pre_hash,
+            max_input_len: repr.max_input_len,
+            ..Default::default()
+        })
     }
 }
@@ -86,6 +86,7 @@ mod base {
         assert!(secretized.verify(TEST_SECRET));
         assert!(!secretized.verify("not-my-secret"));
 
+        #[allow(clippy::needless_borrows_for_generic_args)]
         let secretized = Secret::load(&secretized.to_string()).expect("Invalid secret hash load");
         assert!(secretized.verify(TEST_SECRET));
         assert!(!secretized.verify("not-my-secret"));
@@ -163,8 +164,14 @@ mod test_base64 {
 #[cfg(feature = "serde")]
 mod test_serde {
     use crate::tests::TEST_SECRET;
-    use crate::Secret;
+    use zeroize::Zeroize;
 
+    use crate::{
+        Algorithm, Keyring, Normalization, Params, Pepper, PlainSecret, Secret, SecretConfig,
+        SecretInput, StoredSecret, Version,
+    };
+
+    #[allow(dead_code)]
     #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
     struct TestSecretWrapper {
         secret: Secret,
@@ -204,6 +211,169 @@ mod test_serde {
         let deserialized = serde_json::from_str::<Secret>("\"JGFyZ29uMmlkJHY9MTkkbT0xOTQ1Nix0PTIscD0xJDliNGpYd2xFS1FsenNCVkRHd3JrMWckM0ZiMit5aEJTMU1FSm9BeitTVW5OVmcvMTlTdi8vMTdIUEY5YXVnMForWQ==\"");
         assert!(deserialized.is_ok());
     }
+
+    #[rstest::rstest]
+    fn test_keyring_round_trip() {
+        let config = SecretConfig {
+            keyring: Some(Keyring::new("k1", Pepper::new("my-pepper")).expect("invalid keyring")),
+            ..Default::default()
+        };
+        let secret = Secret::new_with_config(TEST_SECRET, config.clone()).expect("invalid secret");
+        let serialized = serde_json::to_string(&secret).expect("invalid serialization");
+
+        let deserialized =
+            serde_json::from_str::<Secret>(&serialized).expect("invalid deserialization");
+        assert!(!deserialized.verify(TEST_SECRET));
+
+        let deserialized = deserialized.with_config(&config).expect("invalid config");
+        assert!(deserialized.verify(TEST_SECRET));
+        assert!(!deserialized.verify("not-my-secret"));
+    }
+
+    #[rstest::rstest]
+    fn test_deserialize_config() {
+        let config = serde_json::from_str::<SecretConfig>(
+            r#"{"algorithm": "argon2i", "version": 16, "memory_kib": 1024, "iterations": 3, "parallelism": 2, "output_len": 64}"#,
+        )
+        .expect("invalid config");
+        assert_eq!(config.algorithm, Algorithm::Argon2i);
+        assert_eq!(config.version, Version::V0x10);
+        assert_eq!(config.params.m_cost(), 1024);
+        assert_eq!(config.params.t_cost(), 3);
+        assert_eq!(config.params.p_cost(), 2);
+        assert_eq!(config.params.output_len(), Some(64));
+    }
+
+    #[rstest::rstest]
+    fn test_deserialize_config_defaults() {
+        let config = serde_json::from_str::<SecretConfig>(r#"{"memory_kib": 1024}"#)
+            .expect("invalid config");
+        assert_eq!(config.algorithm, Algorithm::Argon2id);
+        assert_eq!(config.version, Version::V0x13);
+        assert_eq!(config.params.m_cost(), 1024);
+        assert_eq!(config.params.t_cost(), Params::DEFAULT_T_COST);
+        assert_eq!(config.params.p_cost(), Params::DEFAULT_P_COST);
+    }
+
+    #[rstest::rstest]
+    #[case::algorithm(r#"{"algorithm": "argon3"}"#, "invalid algorithm `argon3`")]
+    #[case::version(r#"{"version": 20}"#, "invalid version `20`")]
+    #[case::memory(r#"{"memory_kib": 4}"#, "invalid parameters")]
+    #[case::unknown_field(r#"{"memory": 1024}"#, "unknown field `memory`")]
+    #[case::normalization(r#"{"normalization": "nfc"}"#, "invalid normalization `nfc`")]
+    #[case::pre_hash(r#"{"pre_hash": "sha1"}"#, "invalid pre_hash `sha1`")]
+    fn test_deserialize_invalid_config(#[case] input: &str, #[case] message: &str) {
+        let err = serde_json::from_str::<SecretConfig>(input).expect_err("config is valid");
+        assert!(err.to_string().contains(message), "{err}");
+    }
+
+    #[rstest::rstest]
+    fn test_serialize_config() {
+        let config = SecretConfig::default();
+        let serialized = serde_json::to_string(&config).expect("failed to serialize config");
+        let deserialized =
+            serde_json::from_str::<SecretConfig>(&serialized).expect("invalid config");
+        assert_eq!(deserialized.algorithm, config.algorithm);
+        assert_eq!(deserialized.version, config.version);
+        assert_eq!(deserialized.params, config.params);
+        assert!(!serialized.contains("normalization"));
+    }
+
+    #[rstest::rstest]
+    fn test_serialize_config_normalization() {
+        let config = SecretConfig {
+            normalization: Normalization::OpaqueString,
+            ..Default::default()
+        };
+        let serialized = serde_json::to_string(&config).expect("failed to serialize config");
+        assert!(serialized.contains(r#""normalization":"opaque-string""#));
+        let deserialized =
+            serde_json::from_str::<SecretConfig>(&serialized).expect("invalid config");
+        assert_eq!(deserialized.normalization, Normalization::OpaqueString);
+    }
+
+    #[rstest::rstest]
+    fn test_serialize_config_input_limits() {
+        let config = SecretConfig {
+            max_input_len: Some(1024),
+            #[cfg(feature = "prehash")]
+            pre_hash: crate::PreHash::HmacSha512,
+            ..Default::default()
+        };
+        let serialized = serde_json::to_string(&config).expect("failed to serialize config");
+        assert!(serialized.contains(r#""max_input_len":1024"#));
+        let deserialized =
+            serde_json::from_str::<SecretConfig>(&serialized).expect("invalid config");
+        assert_eq!(deserialized.max_input_len, Some(1024));
+        assert_eq!(deserialized.pre_hash, config.pre_hash);
+    }
+
+    #[rstest::rstest]
+    fn test_deserialize_secret_input() {
+        #[derive(serde_derive::Deserialize)]
+        struct Login {
+            password: SecretInput,
+        }
+
+        let login = serde_json::from_str::<Login>(&format!(r#"{{"password": "{TEST_SECRET}"}}"#))
+            .expect("invalid login");
+        assert_eq!(login.password.as_ref(), TEST_SECRET.as_bytes());
+
+        let secret = Secret::new(&login.password).expect("invalid secret");
+        assert!(secret.verify(login.password));
+    }
+
+    #[rstest::rstest]
+    fn test_plain_and_stored_secret() {
+        #[derive(serde_derive::Deserialize)]
+        struct SignUp {
+            password: PlainSecret,
+        }
+
+        #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+        struct UserRow {
+            password: StoredSecret,
+        }
+
+        let sign_up =
+            serde_json::from_str::<SignUp>(&format!(r#"{{"password": "{TEST_SECRET}"}}"#))
+                .expect("invalid sign up");
+        let row = UserRow {
+            password: sign_up.password.into(),
+        };
+        let serialized = serde_json::to_string(&row).expect("failed to serialize row");
+        assert!(!serialized.contains(TEST_SECRET));
+
+        let deserialized = serde_json::from_str::<UserRow>(&serialized).expect("invalid row");
+        assert!(deserialized.password.as_ref().verify(TEST_SECRET));
+        assert_eq!(
+            serde_json::to_string(&deserialized).expect("failed to serialize row"),
+            serialized
+        );
+        assert!(serde_json::from_str::<StoredSecret>(&format!(r#""{TEST_SECRET}""#)).is_err());
+    }
+
+    #[rstest::rstest]
+    fn test_deserialize_secret_input_memory_footprint() {
+        let plaintext = TEST_SECRET.to_string();
+        let address = plaintext.as_ptr();
+        let mut input = serde_json::from_value::<SecretInput>(serde_json::Value::String(plaintext))
+            .expect("invalid secret input");
+        assert_eq!(input.as_ref().as_ptr(), address);
+
+        input.zeroize();
+        let memory = unsafe { std::slice::from_raw_parts(address, TEST_SECRET.len()) };
+        assert!(memory.iter().all(|byte| *byte == 0));
+    }
+
+    #[rstest::rstest]
+    #[case::integer("918273645")]
+    #[case::float("918273.645")]
+    #[case::boolean("true")]
+    fn test_deserialize_secret_input_not_echoed(#[case] input: &str) {
+        let err = serde_json::from_str::<SecretInput>(input).expect_err("secret is valid");
+        assert!(!err.to_string().contains(input), "{err}");
+    }
 }
 
 #[cfg(feature = "openapi")]
@@ -211,8 +381,92 @@ mod test_openapi {
     use poem_openapi::registry::{MetaSchemaRef, Registry};
     use poem_openapi::types::{ParseFromJSON, ToJSON, Type};
 
+    use zeroize::Zeroize;
+
+    use crate::openapi::zeroize_value;
     use crate::tests::TEST_SECRET;
-    use crate::Secret;
+    use crate::{PlainSecret, Secret, SecretInput, StoredSecret};
+
+    #[derive(Debug, poem_openapi::Object)]
+    struct SignUp {
+        #[oai(write_only)]
+        password: PlainSecret,
+    }
+
+    #[derive(Debug, poem_openapi::Object)]
+    struct UserRow {
+        password: StoredSecret,
+    }
+
+    #[rstest::rstest]
+    fn test_openapi_plain_and_stored_secret() {
+        let sign_up = SignUp::parse_from_json(Some(serde_json::json!({ "password": TEST_SECRET })))
+            .expect("invalid sign up");
+        assert_eq!(sign_up.to_json(), Some(serde_json::json!({})));
+
+        let row = UserRow {
+            password: sign_up.password.into(),
+        };
+        let json = row.to_json().expect("failed to serialize row");
+        assert!(!json.to_string().contains(TEST_SECRET));
+
+        let parsed = UserRow::parse_from_json(Some(json.clone())).expect("invalid row");
+        assert!(parsed.password.as_ref().verify(TEST_SECRET));
+        assert_eq!(parsed.to_json(), Some(json));
+        assert!(
+            UserRow::parse_from_json(Some(serde_json::json!({ "password": TEST_SECRET }))).is_err()
+        );
+    }
+
+    #[rstest::rstest]
+    fn test_openapi_secret_input() {
+        let input = SecretInput::parse_from_json(Some(serde_json::json!(TEST_SECRET)))
+            .expect("invalid secret input");
+        assert_eq!(input.as_ref(), TEST_SECRET.as_bytes());
+        assert!(SecretInput::parse_from_json(Some(serde_json::json!(42))).is_err());
+        assert!(matches!(
+            SecretInput::schema_ref(),
+            MetaSchemaRef::Inline(schema) if schema.format == Some("password") && schema.write_only
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_openapi_secret_input_memory_footprint() {
+        let plaintext = TEST_SECRET.to_string();
+        let address = plaintext.as_ptr();
+        let mut input = SecretInput::parse_from_json(Some(serde_json::Value::String(plaintext)))
+            .expect("invalid secret input");
+        assert_eq!(input.as_ref().as_ptr(), address);
+
+        input.zeroize();
+        let memory = unsafe { std::slice::from_raw_parts(address, TEST_SECRET.len()) };
+        assert!(memory.iter().all(|byte| *byte == 0));
+    }
+
+    #[rstest::rstest]
+    #[case::plain_string(serde_json::json!(TEST_SECRET))]
+    #[case::numeric_secret(serde_json::json!({ "secret": 918273645 }))]
+    #[case::nested_secret(serde_json::json!({ "secret": [TEST_SECRET] }))]
+    fn test_openapi_rejected_secret_not_echoed(#[case] value: serde_json::Value) {
+        let message = Secret::parse_from_json(Some(value))
+            .expect_err("secret is valid")
+            .into_message();
+        assert!(!message.contains(TEST_SECRET), "{message}");
+        assert!(!message.contains("918273645"), "{message}");
+    }
+
+    #[rstest::rstest]
+    fn test_zeroize_value() {
+        let mut value = serde_json::json!({
+            "secret": TEST_SECRET,
+            "nested": [TEST_SECRET, { "secret": TEST_SECRET }],
+        });
+        zeroize_value(&mut value);
+        assert_eq!(
+            value,
+            serde_json::json!({ "secret": "", "nested": ["", { "secret": "" }] })
+        );
+    }
 
     #[rstest::rstest]
     fn test_openapi() {
@@ -329,3 +583,1617 @@ mod test_qe {
         assert_ne!(secret, "not-my-secret");
     }
 }
+
+mod test_upgrade {
+    use crate::tests::TEST_SECRET;
+    use crate::{Algorithm, Params, Secret, SecretConfig, Upgrade, Version};
+
+    fn weak_config() -> SecretConfig {
+        SecretConfig {
+            algorithm: Algorithm::Argon2i,
+            version: Version::V0x13,
+            params: Params::new(1024, 1, 1, None).expect("invalid params"),
+            ..Default::default()
+        }
+    }
+
+    #[rstest::rstest]
+    fn test_needs_rehash() {
+        let secret = Secret::new_with_config(TEST_SECRET, weak_config()).expect("invalid secret");
+        assert!(secret.needs_rehash(&SecretConfig::default()));
+        assert!(!secret.needs_rehash(&weak_config()));
+
+        let secret = Secret::new(TEST_SECRET).expect("invalid secret");
+        assert!(!secret.needs_rehash(&SecretConfig::default()));
+        assert!(secret.needs_rehash(&weak_config()));
+    }
+
+    #[rstest::rstest]
+    fn test_verify_and_upgrade() {
+        let secret = Secret::new_with_config(TEST_SECRET, weak_config()).expect("invalid secret");
+
+        let upgraded = secret.verify_and_upgrade(TEST_SECRET, &SecretConfig::default());
+        let upgraded = match upgraded.expect("failed to upgrade") {
+            Upgrade::Upgraded(upgraded) => upgraded,
+            other => panic!("unexpected upgrade result: {other:?}"),
+        };
+        assert!(upgraded.verify(TEST_SECRET));
+        assert!(!upgraded.needs_rehash(&SecretConfig::default()));
+        assert!(matches!(
+            upgraded.verify_and_upgrade(TEST_SECRET, &SecretConfig::default()),
+            Ok(Upgrade::Current)
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_verify_and_upgrade_mismatch() {
+        let secret = Secret::new_with_config(TEST_SECRET, weak_config()).expect("invalid secret");
+        assert!(matches!(
+            secret.verify_and_upgrade("not-my-secret", &SecretConfig::default()),
+            Ok(Upgrade::Mismatch)
+        ));
+    }
+}
+
+mod test_verification {
+    use crate::tests::TEST_SECRET;
+    use crate::{
+        Algorithm, Params, Preset, Secret, SecretConfig, SecretError, Verification, Version,
+    };
+
+    #[rstest::rstest]
+    fn test_try_verify() {
+        let secret = Secret::new(TEST_SECRET).expect("invalid secret");
+        assert_eq!(
+            secret.try_verify(TEST_SECRET).ok(),
+            Some(Verification::Match)
+        );
+        assert_eq!(
+            secret.try_verify("not-my-secret").ok(),
+            Some(Verification::Mismatch)
+        );
+    }
+
+    #[rstest::rstest]
+    fn test_try_verify_needs_rehash() {
+        let config = SecretConfig {
+            algorithm: Algorithm::Argon2id,
+            version: Version::V0x13,
+            params: Params::new(1024, 1, 1, None).expect("invalid params"),
+            ..Default::default()
+        };
+        let secret = Secret::new_with_config(TEST_SECRET, config.clone()).expect("invalid secret");
+        assert_eq!(
+            secret
+                .try_verify_with_config(TEST_SECRET, &SecretConfig::default())
+                .ok(),
+            Some(Verification::NeedsRehash)
+        );
+        assert_eq!(
+            secret.try_verify_with_config(TEST_SECRET, &config).ok(),
+            Some(Verification::Match)
+        );
+        assert_eq!(
+            secret.try_verify(TEST_SECRET).ok(),
+            Some(Verification::Match)
+        );
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_try_verify_stronger_preset() {
+        let secret = Secret::new_with_config(TEST_SECRET, Preset::Owasp46MiB.into())
+            .expect("invalid secret");
+        assert_eq!(
+            secret.try_verify(TEST_SECRET).ok(),
+            Some(Verification::Match)
+        );
+        assert_eq!(
+            secret.try_verify("not-my-secret").ok(),
+            Some(Verification::Mismatch)
+        );
+    }
+
+    #[rstest::rstest]
+    fn test_try_verify_corrupt_hash() {
+        let secret = Secret::load(
+            "$argon2id$v=19$m=1024,t=1,p=1$c2FsdA$EVfkzdbkxEq5wvvajH66helPj12WjcVw4hcGHquNwSk",
+        )
+        .expect("invalid secret hash");
+        assert!(matches!(
+            secret.try_verify(TEST_SECRET),
+            Err(SecretError::CorruptHash(_))
+        ));
+        assert!(!secret.verify(TEST_SECRET));
+    }
+}
+
+#[cfg(feature = "bcrypt")]
+mod test_bcrypt {
+    use crate::tests::TEST_SECRET;
+    use crate::{Backend, Hasher, Normalization, Secret, SecretConfig, SecretError};
+
+    #[rstest::rstest]
+    fn test_bcrypt_generated() {
+        let config = SecretConfig {
+            backend: Backend::Bcrypt { cost: 4 },
+            ..Default::default()
+        };
+        let secret = Secret::new_with_config(TEST_SECRET, config).expect("invalid secret");
+        assert!(secret.to_string().starts_with("$2b$04$"));
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+        assert!(secret.needs_rehash(&SecretConfig::default()));
+    }
+
+    #[rstest::rstest]
+    fn test_bcrypt_rejects_normalization() {
+        let config = SecretConfig {
+            backend: Backend::Bcrypt { cost: 4 },
+            normalization: Normalization::Nfkc,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Secret::new_with_config(TEST_SECRET, config.clone()),
+            Err(SecretError::UnsupportedByBackend("normalization"))
+        ));
+        assert!(matches!(
+            Hasher::new(config).hash(TEST_SECRET),
+            Err(SecretError::UnsupportedByBackend("normalization"))
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_bcrypt_load() {
+        let secret = Secret::load("$2b$04$LiNDJOw1usoElsTVs2PepeEaBKG.FuxWBpieWBZ0iD192gFB9h.Nu")
+            .expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+    }
+}
+
+#[cfg(feature = "scrypt")]
+mod test_scrypt {
+    use crate::tests::TEST_SECRET;
+    use crate::{Backend, Normalization, Secret, SecretConfig, SecretError};
+
+    #[rstest::rstest]
+    fn test_scrypt_generated() {
+        let config = SecretConfig {
+            backend: Backend::Scrypt(scrypt::Params::new(10, 8, 1, 32).expect("invalid params")),
+            ..Default::default()
+        };
+        let secret = Secret::new_with_config(TEST_SECRET, config).expect("invalid secret");
+        assert!(secret.to_string().starts_with("$scrypt$"));
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+        assert!(secret.needs_rehash(&SecretConfig::default()));
+    }
+
+    #[rstest::rstest]
+    fn test_scrypt_rejects_normalization() {
+        let config = SecretConfig {
+            backend: Backend::Scrypt(scrypt::Params::new(10, 8, 1, 32).expect("invalid params")),
+            normalization: Normalization::OpaqueString,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Secret::new_with_config(TEST_SECRET, config),
+            Err(SecretError::UnsupportedByBackend("normalization"))
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_scrypt_load() {
+        let secret = Secret::load("$scrypt$ln=10,r=8,p=1$c2VjcmV0aXplLXNhbHQxNg$4rkFqDxqDB0u8ESig1KsZR5v7/NBbQ3GzYVusP5VSOA").expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+    }
+}
+
+#[cfg(feature = "pbkdf2")]
+mod test_pbkdf2 {
+    use crate::tests::TEST_SECRET;
+    use crate::{Backend, Secret, SecretConfig};
+
+    #[rstest::rstest]
+    fn test_pbkdf2_generated() {
+        let config = SecretConfig {
+            backend: Backend::Pbkdf2(pbkdf2::Params {
+                rounds: 1000,
+                output_length: 32,
+            }),
+            ..Default::default()
+        };
+        let secret = Secret::new_with_config(TEST_SECRET, config).expect("invalid secret");
+        assert!(secret.to_string().starts_with("$pbkdf2-sha256$"));
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+        assert!(secret.needs_rehash(&SecretConfig::default()));
+    }
+
+    #[rstest::rstest]
+    fn test_pbkdf2_load() {
+        let secret = Secret::load("$pbkdf2-sha256$i=1000,l=32$c2VjcmV0aXplLXNhbHQxNg$9vsHvRkJFpP3Dm4NPYi+eDakS2n9h2VmJ6GgqKLhtbc").expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+    }
+}
+
+#[cfg(feature = "legacy")]
+mod test_legacy {
+    use crate::tests::TEST_SECRET;
+    use crate::{Secret, SecretConfig};
+
+    const SHA256_HEX: &str = "186ef76e9d6a723ecb570d4d9c287487d001e5d35f7ed4a313350a407950318e";
+
+    #[rstest::rstest]
+    #[case::lowercase(SHA256_HEX.to_string())]
+    #[case::uppercase(SHA256_HEX.to_uppercase())]
+    fn test_migrate_sha256(#[case] legacy: String) {
+        let secret = Secret::migrate(legacy, TEST_SECRET, SecretConfig::default())
+            .expect("failed to migrate")
+            .expect("legacy secret mismatch");
+        assert!(secret.to_string().starts_with("$argon2id$"));
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.needs_rehash(&SecretConfig::default()));
+    }
+
+    #[rstest::rstest]
+    fn test_migrate_mismatch() {
+        let secret = Secret::migrate(SHA256_HEX, "not-my-secret", SecretConfig::default());
+        assert!(matches!(secret, Ok(None)));
+    }
+
+    #[rstest::rstest]
+    fn test_migrate_invalid_legacy_hash() {
+        let secret = Secret::migrate("not-a-hash", TEST_SECRET, SecretConfig::default());
+        assert!(secret.is_err());
+    }
+
+    #[cfg(feature = "bcrypt")]
+    #[rstest::rstest]
+    fn test_migrate_bcrypt() {
+        let secret = Secret::migrate(
+            "$2b$04$LiNDJOw1usoElsTVs2PepeEaBKG.FuxWBpieWBZ0iD192gFB9h.Nu",
+            TEST_SECRET,
+            SecretConfig::default(),
+        )
+        .expect("failed to migrate")
+        .expect("legacy secret mismatch");
+        assert!(secret.to_string().starts_with("$argon2id$"));
+        assert!(secret.verify(TEST_SECRET));
+    }
+}
+
+mod test_pepper {
+    use crate::tests::TEST_SECRET;
+    use crate::{Keyring, Pepper, Secret, SecretConfig, SecretError, Upgrade, Verification};
+
+    fn peppered_config(pepper: &str) -> SecretConfig {
+        SecretConfig {
+            pepper: Some(Pepper::new(pepper)),
+            ..Default::default()
+        }
+    }
+
+    #[rstest::rstest]
+    fn test_peppered_secret() {
+        let secret = Secret::new_with_config(TEST_SECRET, peppered_config("my-pepper"))
+            .expect("invalid secret");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+    }
+
+    #[rstest::rstest]
+    fn test_peppered_secret_load() {
+        let secretized = Secret::new_with_config(TEST_SECRET, peppered_config("my-pepper"))
+            .expect("invalid secret")
+            .to_string();
+
+        let secret = Secret::load_with_config(&secretized, &peppered_config("my-pepper"))
+            .expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+
+        let secret = Secret::load_with_config(&secretized, &peppered_config("not-my-pepper"))
+            .expect("invalid secret hash");
+        assert!(!secret.verify(TEST_SECRET));
+
+        let secret = Secret::load(&secretized).expect("invalid secret hash");
+        assert!(!secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_pepper_debug() {
+        let config = peppered_config("my-pepper");
+        assert!(!format!("{config:?}").contains("my-pepper"));
+    }
+
+    fn keyring_config(keyring: Keyring) -> SecretConfig {
+        SecretConfig {
+            keyring: Some(keyring),
+            ..Default::default()
+        }
+    }
+
+    #[rstest::rstest]
+    fn test_keyring_secret() {
+        let keyring = Keyring::new("k1", Pepper::new("first-pepper")).expect("invalid keyring");
+        let secretized = Secret::new_with_config(TEST_SECRET, keyring_config(keyring.clone()))
+            .expect("invalid secret")
+            .to_string();
+        assert!(secretized.contains("keyid="));
+
+        let secret = Secret::load_with_config(&secretized, &keyring_config(keyring))
+            .expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+    }
+
+    #[rstest::rstest]
+    fn test_keyring_rotation() {
+        let mut keyring = Keyring::new("k1", Pepper::new("first-pepper")).expect("invalid keyring");
+        let secretized = Secret::new_with_config(TEST_SECRET, keyring_config(keyring.clone()))
+            .expect("invalid secret")
+            .to_string();
+
+        keyring
+            .rotate("k2", Pepper::new("second-pepper"))
+            .expect("invalid key id");
+        let config = keyring_config(keyring);
+        let secret = Secret::load_with_config(&secretized, &config).expect("invalid secret hash");
+        assert_eq!(
+            secret.try_verify_with_config(TEST_SECRET, &config).ok(),
+            Some(Verification::NeedsRehash)
+        );
+
+        let upgraded = match secret.verify_and_upgrade(TEST_SECRET, &config) {
+            Ok(Upgrade::Upgraded(upgraded)) => upgraded,
+            other => panic!("unexpected upgrade result: {other:?}"),
+        };
+        let upgraded =
+            Secret::load_with_config(upgraded.to_string(), &config).expect("invalid secret hash");
+        assert_eq!(
+            upgraded.try_verify_with_config(TEST_SECRET, &config).ok(),
+            Some(Verification::Match)
+        );
+    }
+
+    #[rstest::rstest]
+    fn test_keyring_unknown_key_id() {
+        let keyring = Keyring::new("k1", Pepper::new("first-pepper")).expect("invalid keyring");
+        let secretized = Secret::new_with_config(TEST_SECRET, keyring_config(keyring))
+            .expect("invalid secret")
+            .to_string();
+
+        let keyring = Keyring::new("k2", Pepper::new("second-pepper")).expect("invalid keyring");
+        assert!(matches!(
+            Secret::load_with_config(&secretized, &keyring_config(keyring)),
+            Err(SecretError::UnknownKeyId)
+        ));
+        let secret = Secret::load(&secretized).expect("invalid secret hash");
+        assert!(matches!(
+            secret.try_verify(TEST_SECRET),
+            Err(SecretError::UnknownKeyId)
+        ));
+        assert!(!secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_keyring_attach() {
+        let keyring = Keyring::new("k1", Pepper::new("first-pepper")).expect("invalid keyring");
+        let secretized = Secret::new_with_config(TEST_SECRET, keyring_config(keyring.clone()))
+            .expect("invalid secret")
+            .to_string();
+
+        let secret = secretized
+            .parse::<Secret>()
+            .expect("invalid secret hash")
+            .with_config(&keyring_config(keyring))
+            .expect("invalid config");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(!secret.verify("not-my-secret"));
+
+        let secret = Secret::load(&secretized)
+            .and_then(|secret| secret.with_config(&peppered_config("first-pepper")))
+            .expect("invalid secret hash");
+        assert!(!secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_keyring_invalid_key_id() {
+        assert!(matches!(
+            Keyring::new("too-long-key-id", Pepper::new("my-pepper")),
+            Err(SecretError::InvalidKeyId(_))
+        ));
+    }
+}
+
+#[cfg(feature = "async")]
+mod test_async {
+    use crate::tests::TEST_SECRET;
+    use crate::{Secret, SecretError};
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_new_and_verify_async() {
+        let secret = Secret::new_async(TEST_SECRET)
+            .await
+            .expect("invalid secret");
+        assert!(secret.verify_async(TEST_SECRET).await);
+        assert!(!secret.verify_async("not-my-secret").await);
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_concurrent_verify_async() {
+        let secret = Secret::new_async(TEST_SECRET)
+            .await
+            .expect("invalid secret");
+        let verifications = (0..4)
+            .map(|_| {
+                let secret = secret.clone();
+                tokio::spawn(async move { secret.verify_async(TEST_SECRET).await })
+            })
+            .collect::<Vec<_>>();
+        for verification in verifications {
+            assert!(verification.await.expect("verification task failed"));
+        }
+    }
+
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_limit_async_concurrency_once() {
+        let _ = Secret::new_async(TEST_SECRET).await;
+        assert!(matches!(
+            Secret::limit_async_concurrency(1),
+            Err(SecretError::ConcurrencyLimitAlreadySet)
+        ));
+    }
+}
+
+mod test_pool {
+    use std::thread;
+
+    use crate::tests::TEST_SECRET;
+    use crate::{Secret, SecretConfig, SecretError, SecretPool, Verification};
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        while !condition() {
+            thread::yield_now();
+        }
+    }
+
+    #[rstest::rstest]
+    fn test_pool_hash_and_verify() {
+        let pool = SecretPool::new(SecretConfig::default(), 2);
+        let secret = pool.hash(TEST_SECRET).expect("invalid secret");
+        assert_eq!(
+            pool.verify(&secret, TEST_SECRET).ok(),
+            Some(Verification::Match)
+        );
+        assert_eq!(
+            pool.verify(&secret, "not-my-secret").ok(),
+            Some(Verification::Mismatch)
+        );
+        assert_eq!(pool.in_flight(), 0);
+        assert_eq!(pool.queue_depth(), 0);
+    }
+
+    #[rstest::rstest]
+    fn test_pool_limits_concurrency() {
+        let pool = SecretPool::new(SecretConfig::default(), 1);
+        let secret = Secret::new(TEST_SECRET).expect("invalid secret");
+
+        let running = {
+            let (pool, secret) = (pool.clone(), secret.clone());
+            thread::spawn(move || pool.verify(&secret, TEST_SECRET))
+        };
+        wait_until(|| pool.in_flight() == 1);
+        assert!(matches!(
+            pool.try_hash(TEST_SECRET),
+            Err(SecretError::PoolExhausted)
+        ));
+
+        let queued = {
+            let pool = pool.clone();
+            thread::spawn(move || pool.hash(TEST_SECRET))
+        };
+        wait_until(|| pool.queue_depth() == 1 || queued.is_finished());
+
+        assert!(running.join().expect("verification panicked").is_ok());
+        assert!(queued.join().expect("hashing panicked").is_ok());
+        assert_eq!(pool.in_flight(), 0);
+        assert_eq!(pool.queue_depth(), 0);
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_pool_async() {
+        let pool = SecretPool::new(SecretConfig::default(), 1);
+        let secret = pool.hash_async(TEST_SECRET).await.expect("invalid secret");
+        assert_eq!(
+            pool.verify_async(&secret, TEST_SECRET).await.ok(),
+            Some(Verification::Match)
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn test_pool_async_exhausted() {
+        let pool = SecretPool::new(SecretConfig::default(), 1);
+        let secret = Secret::new(TEST_SECRET).expect("invalid secret");
+
+        let running = {
+            let (pool, secret) = (pool.clone(), secret.clone());
+            thread::spawn(move || pool.verify(&secret, TEST_SECRET))
+        };
+        wait_until(|| pool.in_flight() == 1);
+        assert!(matches!(
+            pool.try_hash_async(TEST_SECRET).await,
+            Err(SecretError::PoolExhausted)
+        ));
+        assert!(matches!(
+            pool.try_verify_async(&secret, TEST_SECRET).await,
+            Err(SecretError::PoolExhausted)
+        ));
+
+        let queued = {
+            let pool = pool.clone();
+            tokio::spawn(async move { pool.hash_async(TEST_SECRET).await })
+        };
+        while pool.queue_depth() == 0 && !queued.is_finished() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(running.join().expect("verification panicked").is_ok());
+        assert!(queued.await.expect("hashing task failed").is_ok());
+        assert_eq!(pool.in_flight(), 0);
+        assert_eq!(pool.queue_depth(), 0);
+    }
+}
+
+mod test_hasher {
+    use crate::tests::TEST_SECRET;
+    use crate::{Hasher, Pepper, Secret, SecretConfig};
+
+    #[rstest::rstest]
+    fn test_hasher_hash() {
+        let hasher = Hasher::default();
+        let secret = hasher.hash(TEST_SECRET).expect("invalid secret");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(secret.verify_with(&hasher, TEST_SECRET));
+        assert!(!secret.verify_with(&hasher, "not-my-secret"));
+
+        let secret = Secret::load(secret.to_string()).expect("invalid secret hash");
+        assert!(secret.verify_with(&hasher, TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_hasher_verify_with() {
+        let hasher = Hasher::default();
+        let secret = Secret::new(TEST_SECRET).expect("invalid secret");
+        for _ in 0..2 {
+            assert!(secret.verify_with(&hasher, TEST_SECRET));
+            assert!(!secret.verify_with(&hasher, "not-my-secret"));
+        }
+    }
+
+    #[rstest::rstest]
+    fn test_hasher_with_pepper() {
+        let config = SecretConfig {
+            pepper: Some(Pepper::new("my-pepper")),
+            ..Default::default()
+        };
+        let hasher = Hasher::new(config.clone());
+        let secret = hasher.hash(TEST_SECRET).expect("invalid secret");
+        assert!(secret.verify_with(&hasher, TEST_SECRET));
+
+        let secret =
+            Secret::load_with_config(secret.to_string(), &config).expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+        let secret = Secret::load(secret.to_string()).expect("invalid secret hash");
+        assert!(!secret.verify_with(&hasher, TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_hasher_verify_with_max_input_len() {
+        let secret = Secret::new(TEST_SECRET).expect("invalid secret");
+        let hasher = Hasher::new(SecretConfig {
+            max_input_len: Some(TEST_SECRET.len() - 1),
+            ..Default::default()
+        });
+        assert!(!secret.verify_with(&hasher, TEST_SECRET));
+        assert!(secret.verify_with(&Hasher::default(), TEST_SECRET));
+    }
+}
+
+mod test_calibrate {
+    use std::time::Duration;
+
+    use crate::tests::TEST_SECRET;
+    use crate::{Secret, SecretConfig, SecretError};
+
+    #[rstest::rstest]
+    fn test_calibrate() {
+        let config =
+            SecretConfig::calibrate(Duration::from_millis(250), 2048).expect("failed to calibrate");
+        assert!(config.params.m_cost() <= 2048);
+        assert!(config.params.t_cost() >= 1);
+
+        let secret = Secret::new_with_config(TEST_SECRET, config).expect("invalid secret");
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_calibrate_unreachable_target() {
+        assert!(matches!(
+            SecretConfig::calibrate(Duration::ZERO, 2048),
+            Err(SecretError::CalibrationTarget(_))
+        ));
+    }
+}
+
+mod test_preset {
+    use crate::tests::TEST_SECRET;
+    use crate::{Algorithm, Preset, Secret, SecretConfig};
+
+    #[rstest::rstest]
+    #[case::rfc9106_first(Preset::Rfc9106FirstRecommended, 2 * 1024 * 1024, 1, 4)]
+    #[case::rfc9106_second(Preset::Rfc9106SecondRecommended, 64 * 1024, 3, 4)]
+    #[case::owasp_46mib(Preset::Owasp46MiB, 47104, 1, 1)]
+    #[case::owasp_19mib(Preset::Owasp19MiB, 19456, 2, 1)]
+    #[case::owasp_12mib(Preset::Owasp12MiB, 12288, 3, 1)]
+    #[case::owasp_9mib(Preset::Owasp9MiB, 9216, 4, 1)]
+    #[case::owasp_7mib(Preset::Owasp7MiB, 7168, 5, 1)]
+    #[case::testing(Preset::Testing, 8, 1, 1)]
+    fn test_preset_params(
+        #[case] preset: Preset,
+        #[case] m_cost: u32,
+        #[case] t_cost: u32,
+        #[case] p_cost: u32,
+    ) {
+        let config = SecretConfig::from(preset);
+        assert_eq!(config.algorithm, Algorithm::Argon2id);
+        assert_eq!(config.params.m_cost(), m_cost);
+        assert_eq!(config.params.t_cost(), t_cost);
+        assert_eq!(config.params.p_cost(), p_cost);
+    }
+
+    #[rstest::rstest]
+    fn test_preset_secret() {
+        let secret = Secret::new_with_config(TEST_SECRET, SecretConfig::preset(Preset::Testing))
+            .expect("invalid secret");
+        assert!(secret.verify(TEST_SECRET));
+        assert!(secret.needs_rehash(&SecretConfig::preset(Preset::Owasp19MiB)));
+    }
+}
+
+mod test_load_policy {
+    use crate::tests::TEST_SECRET;
+    use crate::{Algorithm, LoadPolicy, Params, Preset, Secret, SecretConfig, SecretError};
+
+    fn argon2d_secret() -> String {
+        let config = SecretConfig {
+            algorithm: Algorithm::Argon2d,
+            params: Preset::Testing.params(),
+            ..Default::default()
+        };
+        Secret::new_with_config(TEST_SECRET, config)
+            .expect("invalid secret")
+            .to_string()
+    }
+
+    fn policy_config(policy: LoadPolicy) -> SecretConfig {
+        SecretConfig {
+            load_policy: Some(policy),
+            ..Default::default()
+        }
+    }
+
+    #[rstest::rstest]
+    fn test_load_policy_accepts() {
+        let secretized = Secret::new(TEST_SECRET)
+            .expect("invalid secret")
+            .to_string();
+        let policy = LoadPolicy {
+            algorithm: Algorithm::Argon2id,
+            m_cost: Params::DEFAULT_M_COST,
+            salt_len: 16,
+            output_len: 32,
+            allow_other_backends: false,
+            ..Default::default()
+        };
+        let secret = Secret::load_with_config(secretized, &policy_config(policy))
+            .expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    #[case::algorithm(LoadPolicy { algorithm: Algorithm::Argon2id, ..Default::default() }, "algorithm")]
+    #[case::m_cost(LoadPolicy { m_cost: 1024, ..Default::default() }, "m_cost")]
+    #[case::t_cost(LoadPolicy { t_cost: 2, ..Default::default() }, "t_cost")]
+    #[case::salt_len(LoadPolicy { salt_len: 32, ..Default::default() }, "salt_len")]
+    #[case::output_len(LoadPolicy { output_len: 64, ..Default::default() }, "output_len")]
+    fn test_load_policy_rejects(#[case] policy: LoadPolicy, #[case] field: &str) {
+        let secret = Secret::load_with_config(argon2d_secret(), &policy_config(policy));
+        assert!(matches!(secret, Err(SecretError::LoadPolicy(violation)) if violation == field));
+    }
+
+    #[rstest::rstest]
+    fn test_load_policy_override() {
+        let strict = LoadPolicy {
+            algorithm: Algorithm::Argon2i,
+            ..Default::default()
+        };
+        assert!(Secret::load_with_config(argon2d_secret(), &policy_config(strict)).is_err());
+
+        let secret = Secret::load_with_config(argon2d_secret(), &policy_config(Default::default()))
+            .expect("invalid secret hash");
+        assert!(secret.verify(TEST_SECRET));
+    }
+}
+
+mod test_env {
+    use crate::tests::TEST_SECRET;
+    use crate::{Algorithm, Normalization, Params, PreHash, Secret, SecretConfig, SecretError};
+
+    #[rstest::rstest]
+    fn test_from_env_defaults() {
+        let config = SecretConfig::from_env("SECRETIZE_TEST_DEFAULTS").expect("invalid config");
+        assert_eq!(config.algorithm, Algorithm::default());
+        assert_eq!(config.params, Params::default());
+        assert!(config.pepper.is_none());
+    }
+
+    #[rstest::rstest]
+    fn test_from_env() {
+        let pepper_file = std::env::temp_dir().join("secretize-test-from-env-pepper");
+        std::fs::write(&pepper_file, "pepper\n").expect("failed to write pepper file");
+        std::env::set_var("SECRETIZE_TEST_ENV_ALGORITHM", "argon2i");
+        std::env::set_var("SECRETIZE_TEST_ENV_M_COST", "64");
+        std::env::set_var("SECRETIZE_TEST_ENV_T_COST", "2");
+        std::env::set_var("SECRETIZE_TEST_ENV_P_COST", "1");
+        std::env::set_var("SECRETIZE_TEST_ENV_PEPPER_FILE", &pepper_file);
+        std::env::set_var("SECRETIZE_TEST_ENV_NORMALIZATION", "nfkc");
+        std::env::set_var("SECRETIZE_TEST_ENV_PRE_HASH", "none");
+        std::env::set_var("SECRETIZE_TEST_ENV_MAX_INPUT_LEN", "1024");
+
+        let config = SecretConfig::from_env("SECRETIZE_TEST_ENV").expect("invalid config");
+        assert_eq!(config.algorithm, Algorithm::Argon2i);
+        assert_eq!(config.params.m_cost(), 64);
+        assert_eq!(config.params.t_cost(), 2);
+        assert_eq!(config.params.p_cost(), 1);
+        assert_eq!(config.normalization, Normalization::Nfkc);
+        assert_eq!(config.pre_hash, PreHash::None);
+        assert_eq!(config.max_input_len, Some(1024));
+        assert_eq!(
+            config.pepper.as_ref().map(AsRef::as_ref),
+            Some(&b"pepper"[..])
+        );
+
+        let secret = Secret::new_with_config(TEST_SECRET, config).expect("invalid secret");
+        assert!(secret.verify(TEST_SECRET));
+        std::fs::remove_file(pepper_file).expect("failed to remove pepper file");
+    }
+
+    #[rstest::rstest]
+    #[case::algorithm("SECRETIZE_TEST_ALGORITHM", "ALGORITHM", "argon3")]
+    #[case::m_cost("SECRETIZE_TEST_M_COST", "M_COST", "1")]
+    #[case::t_cost("SECRETIZE_TEST_T_COST", "T_COST", "0")]
+    #[case::p_cost("SECRETIZE_TEST_P_COST", "P_COST", "many")]
+    #[case::pepper_file("SECRETIZE_TEST_PEPPER_FILE", "PEPPER_FILE", "/nonexistent/pepper")]
+    #[case::normalization("SECRETIZE_TEST_NORMALIZATION", "NORMALIZATION", "nfc")]
+    #[case::pre_hash("SECRETIZE_TEST_PRE_HASH", "PRE_HASH", "sha1")]
+    #[case::max_input_len("SECRETIZE_TEST_MAX_INPUT_LEN", "MAX_INPUT_LEN", "many")]
+    fn test_from_env_invalid(#[case] prefix: &str, #[case] suffix: &str, #[case] value: &str) {
+        let name = format!("{prefix}_{suffix}");
+        std::env::set_var(&name, value);
+        assert!(matches!(
+            SecretConfig::from_env(prefix),
+            Err(SecretError::InvalidEnvVar(invalid)) if invalid == name
+        ));
+    }
+}
+
+mod test_salt_len {
+    use crate::tests::TEST_SECRET;
+    use crate::{Hasher, Params, Secret, SecretConfig, SecretError};
+
+    fn config(salt_len: usize, output_len: usize) -> SecretConfig {
+        SecretConfig {
+            params: Params::new(64, 1, 1, Some(output_len)).expect("invalid params"),
+            salt_len: Some(salt_len),
+            ..Default::default()
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::short(8, 32)]
+    #[case::compliance(32, 64)]
+    #[case::max(48, 64)]
+    fn test_salt_and_output_len(#[case] salt_len: usize, #[case] output_len: usize) {
+        let secretized = Secret::new_with_config(TEST_SECRET, config(salt_len, output_len))
+            .expect("invalid secret")
+            .to_string();
+
+        let secret = Secret::load(&secretized).expect("failed to load secret");
+        assert!(secret.verify(TEST_SECRET));
+        assert_eq!(secret.to_string(), secretized);
+
+        let loaded = secret.secret.config().expect("invalid config");
+        assert_eq!(loaded.salt_len, Some(salt_len));
+        assert_eq!(loaded.params.output_len(), Some(output_len));
+        assert!(!secret.needs_rehash(&config(salt_len, output_len)));
+    }
+
+    #[rstest::rstest]
+    fn test_hasher_salt_len() {
+        let secret = Hasher::new(config(32, 64))
+            .hash(TEST_SECRET)
+            .expect("invalid secret");
+        let loaded = secret.secret.config().expect("invalid config");
+        assert_eq!(loaded.salt_len, Some(32));
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_shorter_salt_needs_rehash() {
+        let secret = Secret::new_with_config(TEST_SECRET, config(16, 32)).expect("invalid secret");
+        assert!(secret.needs_rehash(&config(32, 32)));
+    }
+
+    #[rstest::rstest]
+    #[case::too_short(4)]
+    #[case::too_long(49)]
+    fn test_invalid_salt_len(#[case] salt_len: usize) {
+        assert!(matches!(
+            Secret::new_with_config(TEST_SECRET, config(salt_len, 32)),
+            Err(SecretError::Hashing(_))
+        ));
+    }
+}
+
+#[cfg(feature = "deterministic")]
+mod test_deterministic {
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use crate::tests::TEST_SECRET;
+    use crate::{Preset, Secret, SecretError};
+
+    #[rstest::rstest]
+    fn test_new_with_rng() {
+        let secret = |seed| {
+            Secret::new_with_rng(
+                TEST_SECRET,
+                Preset::Testing.into(),
+                &mut ChaCha20Rng::seed_from_u64(seed),
+            )
+            .expect("invalid secret")
+        };
+        assert_eq!(secret(42).to_string(), secret(42).to_string());
+        assert_ne!(secret(42).to_string(), secret(43).to_string());
+        assert!(secret(42).verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_new_with_salt() {
+        let secret =
+            Secret::new_with_salt(TEST_SECRET, Preset::Testing.into(), b"secretize-salt16")
+                .expect("invalid secret");
+        assert_eq!(
+            secret.to_string(),
+            "$argon2id$v=19$m=8,t=1,p=1$c2VjcmV0aXplLXNhbHQxNg$5RycExgJZkRmM3TVSlkPd867GEgAMrCm7FfHqveu05k"
+        );
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_new_with_invalid_salt() {
+        assert!(matches!(
+            Secret::new_with_salt(TEST_SECRET, Preset::Testing.into(), b"abc"),
+            Err(SecretError::Hashing(_))
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_new_with_salt_checks_input_len_first() {
+        use crate::{PasswordPolicy, SecretConfig};
+
+        let config = SecretConfig {
+            max_input_len: Some(4),
+            password_policy: Some(PasswordPolicy {
+                min_length: 100,
+                ..Default::default()
+            }),
+            ..Preset::Testing.into()
+        };
+        assert!(matches!(
+            Secret::new_with_salt(TEST_SECRET, config, b"secretize-salt16"),
+            Err(SecretError::InputTooLong(4))
+        ));
+    }
+}
+
+mod test_input {
+    use zeroize::Zeroize;
+
+    use crate::tests::TEST_SECRET;
+    use crate::{Secret, SecretInput};
+
+    #[rstest::rstest]
+    fn test_secret_input() {
+        let input = SecretInput::from(TEST_SECRET.to_string());
+        assert_eq!(input.len(), TEST_SECRET.len());
+        assert_eq!(format!("{input:?}"), "SecretInput(..)");
+
+        let secret = Secret::new(&input).expect("invalid secret");
+        assert!(secret.verify(&input));
+        assert!(secret.verify(SecretInput::new(TEST_SECRET)));
+        assert!(!secret.verify(SecretInput::from("not-my-secret")));
+    }
+
+    #[rstest::rstest]
+    fn test_wrap_secret_input() {
+        let secret = Secret::wrap(SecretInput::from(TEST_SECRET)).expect("invalid secret");
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_zeroize_secret_input() {
+        let mut input = SecretInput::new(TEST_SECRET);
+        input.zeroize();
+        assert!(input.is_empty());
+    }
+
+    #[cfg(feature = "mlock")]
+    #[rstest::rstest]
+    fn test_secret_input_mlock() {
+        assert!(SecretInput::new(TEST_SECRET).is_locked());
+        assert!(!SecretInput::new("").is_locked());
+    }
+}
+
+mod test_payload {
+    use crate::tests::TEST_SECRET;
+    use crate::{Keyring, Pepper, PlainSecret, Secret, SecretConfig, StoredSecret};
+
+    #[rstest::rstest]
+    fn test_plain_secret_into_stored_secret() {
+        let plain = PlainSecret::new(TEST_SECRET).expect("invalid secret");
+        assert!(plain.as_ref().verify(TEST_SECRET));
+
+        let stored = StoredSecret::from(plain);
+        let loaded = StoredSecret::load(stored.as_ref().to_string()).expect("invalid secret");
+        assert_eq!(loaded.as_ref().to_string(), stored.as_ref().to_string());
+        assert!(Secret::from(loaded).verify(TEST_SECRET));
+        assert!(StoredSecret::load(TEST_SECRET).is_err());
+    }
+
+    #[rstest::rstest]
+    fn test_payload_with_config() {
+        let keyring = Keyring::new("k1", Pepper::new("my-pepper")).expect("invalid keyring");
+        let config = SecretConfig {
+            keyring: Some(keyring),
+            ..Default::default()
+        };
+        let plain =
+            PlainSecret::new_with_config(TEST_SECRET, config.clone()).expect("invalid secret");
+        let secretized = plain.as_ref().to_string();
+        assert!(secretized.contains("keyid="));
+
+        let stored =
+            StoredSecret::load_with_config(&secretized, &config).expect("invalid secret hash");
+        assert!(stored.as_ref().verify(TEST_SECRET));
+
+        let stored = StoredSecret::load(&secretized).expect("invalid secret hash");
+        assert!(!stored.as_ref().verify(TEST_SECRET));
+        let stored = stored.with_config(&config).expect("invalid config");
+        assert!(stored.as_ref().verify(TEST_SECRET));
+    }
+}
+
+mod test_password_policy {
+    use crate::tests::TEST_SECRET;
+    use crate::{
+        Hasher, PasswordPolicy, PasswordViolation, Preset, Secret, SecretConfig, SecretError,
+        SecretPool, Upgrade,
+    };
+
+    fn strict_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            max_length: Some(16),
+            max_bytes: Some(32),
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            banned: vec!["Passw0rd!".to_string()],
+            #[cfg(feature = "entropy")]
+            min_entropy: None,
+            #[cfg(feature = "breach")]
+            breached: None,
+        }
+    }
+
+    fn policy_config(policy: PasswordPolicy) -> SecretConfig {
+        SecretConfig {
+            password_policy: Some(policy),
+            ..Preset::Testing.into()
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::too_many_bytes(
+        b"Ab1!Ab1!Ab1!Ab1!Ab1!Ab1!Ab1!Ab1!Ab1!",
+        PasswordViolation::TooManyBytes(32)
+    )]
+    #[case::invalid_utf8(b"Ab1!\xff\xfeAb1!", PasswordViolation::InvalidUtf8)]
+    #[case::too_short(b"Ab1!", PasswordViolation::TooShort(8))]
+    #[case::too_long(b"Ab1!Ab1!Ab1!Ab1!Ab1!", PasswordViolation::TooLong(16))]
+    #[case::lowercase(b"AB1!AB1!", PasswordViolation::MissingLowercase)]
+    #[case::uppercase(b"ab1!ab1!", PasswordViolation::MissingUppercase)]
+    #[case::digit(b"abC!abC!", PasswordViolation::MissingDigit)]
+    #[case::symbol(b"abC1abC1", PasswordViolation::MissingSymbol)]
+    #[case::banned(b"pASSW0RD!", PasswordViolation::Banned)]
+    fn test_password_policy_violation(
+        #[case] password: &[u8],
+        #[case] violation: PasswordViolation,
+    ) {
+        assert_eq!(strict_policy().validate(password), Err(violation.clone()));
+        assert!(matches!(
+            Secret::new_with_config(password, policy_config(strict_policy())),
+            Err(SecretError::PasswordPolicy(v)) if v == violation
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_password_policy_accepts() {
+        let password = "Corr3ct-Horse";
+        assert_eq!(strict_policy().validate(password.as_bytes()), Ok(()));
+
+        let secret = Secret::wrap_with_config(password.to_string(), policy_config(strict_policy()))
+            .expect("invalid secret");
+        assert!(secret.verify(password));
+        assert!(PasswordPolicy::default()
+            .validate(TEST_SECRET.as_bytes())
+            .is_ok());
+    }
+
+    #[rstest::rstest]
+    fn test_hasher_password_policy() {
+        let hasher = Hasher::new(policy_config(strict_policy()));
+        assert!(matches!(
+            hasher.hash(TEST_SECRET),
+            Err(SecretError::PasswordPolicy(
+                PasswordViolation::MissingUppercase
+            ))
+        ));
+        assert!(hasher.hash("Corr3ct-Horse").is_ok());
+    }
+
+    #[rstest::rstest]
+    fn test_password_policy_skipped_on_upgrade() {
+        let weak =
+            Secret::new_with_config("short", Preset::Testing.into()).expect("invalid secret");
+        let target = SecretConfig {
+            password_policy: Some(PasswordPolicy {
+                min_length: 8,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(Secret::new_with_config("short", target.clone()).is_err());
+
+        let upgraded = match weak.verify_and_upgrade("short", &target) {
+            Ok(Upgrade::Upgraded(upgraded)) => upgraded,
+            other => panic!("unexpected upgrade result: {other:?}"),
+        };
+        assert!(upgraded.verify("short"));
+        assert!(matches!(
+            SecretPool::new(target, 1).hash("short"),
+            Err(SecretError::PasswordPolicy(PasswordViolation::TooShort(8)))
+        ));
+    }
+
+    #[cfg(feature = "entropy")]
+    #[rstest::rstest]
+    #[case::repeated("aaaaaaaaaaaa", false)]
+    #[case::sequential("abcdefghijkl", false)]
+    #[case::short_mixed("aB3$", false)]
+    #[case::long_mixed("c0rReCt-h0rSe-b4tt3ry", true)]
+    fn test_password_policy_entropy(#[case] password: &str, #[case] accepted: bool) {
+        let policy = PasswordPolicy {
+            min_entropy: Some(60),
+            ..Default::default()
+        };
+        assert_eq!(policy.validate(password.as_bytes()).is_ok(), accepted);
+        if !accepted {
+            assert_eq!(
+                policy.validate(password.as_bytes()),
+                Err(PasswordViolation::TooWeak(60))
+            );
+        }
+    }
+}
+
+#[cfg(feature = "breach")]
+mod test_breach {
+    use std::io::Cursor;
+
+    use crate::tests::TEST_SECRET;
+    use crate::{
+        BloomFilter, BreachCorpus, PasswordPolicy, PasswordViolation, Preset, Secret, SecretConfig,
+        SecretError,
+    };
+
+    const HIBP: &str = "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n\
+                        7C4A8D09CA3762AF61E59520943DC26494F8941B:37359195\r\n";
+
+    #[rstest::rstest]
+    fn test_hibp_corpus() {
+        let corpus = BreachCorpus::from_hibp(Cursor::new(HIBP)).expect("invalid corpus");
+        assert!(corpus.contains(b"password"));
+        assert!(corpus.contains(b"123456"));
+        assert!(!corpus.contains(TEST_SECRET.as_bytes()));
+        assert!(matches!(
+            BreachCorpus::from_hibp_file("/nonexistent/pwned-passwords.txt"),
+            Err(SecretError::BreachCorpus(_))
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_hibp_range_corpus() {
+        let range = Cursor::new("1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n");
+        let corpus =
+            BreachCorpus::from_hibp_ranges([("5BAA6".to_string(), range)]).expect("invalid corpus");
+        assert!(corpus.contains(b"password"));
+        assert!(!corpus.contains(TEST_SECRET.as_bytes()));
+
+        let range = Cursor::new("1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n");
+        assert!(matches!(
+            BreachCorpus::from_hibp_ranges([("5BAA".to_string(), range)]),
+            Err(SecretError::BreachCorpus(_))
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_hibp_dir_corpus() {
+        let dir = std::env::temp_dir().join("secretize-test-hibp-ranges");
+        std::fs::create_dir_all(&dir).expect("failed to create range directory");
+        std::fs::write(
+            dir.join("5BAA6"),
+            "1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n",
+        )
+        .expect("failed to write range file");
+        std::fs::write(
+            dir.join("7C4A8.txt"),
+            "D09CA3762AF61E59520943DC26494F8941B:37359195\r\n",
+        )
+        .expect("failed to write range file");
+
+        let corpus = BreachCorpus::from_hibp_dir(&dir).expect("invalid corpus");
+        assert!(corpus.contains(b"password"));
+        assert!(corpus.contains(b"123456"));
+        assert!(!corpus.contains(TEST_SECRET.as_bytes()));
+        assert!(matches!(
+            BreachCorpus::from_hibp_dir(dir.join("5BAA6")),
+            Err(SecretError::BreachCorpus(_))
+        ));
+        assert!(matches!(
+            BreachCorpus::from_hibp_dir("/nonexistent/pwned-passwords"),
+            Err(SecretError::BreachCorpus(_))
+        ));
+        std::fs::remove_dir_all(&dir).expect("failed to remove range directory");
+    }
+
+    #[rstest::rstest]
+    #[case::not_hex("5BAA61E4C9B93F3F0682250B6CF8331B7EE68FDZ:1")]
+    #[case::too_short("5BAA61E4C9B93F3F0682250B6CF8331B7EE68F:1")]
+    #[case::too_long("5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD800:1")]
+    fn test_invalid_hibp_corpus(#[case] corpus: &str) {
+        assert!(matches!(
+            BreachCorpus::from_hibp(Cursor::new(corpus)),
+            Err(SecretError::BreachCorpus(_))
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_bloom_filter() {
+        let mut filter =
+            BloomFilter::from_hibp(Cursor::new(HIBP), 1 << 16, 7).expect("invalid filter");
+        filter.insert(b"hunter3");
+        assert!(filter.contains(b"password"));
+        assert!(filter.contains(b"hunter3"));
+        assert!(!filter.contains(TEST_SECRET.as_bytes()));
+
+        let bytes = filter.to_bytes();
+        assert_eq!(
+            BloomFilter::from_bytes(&bytes).expect("invalid filter"),
+            filter
+        );
+        assert!(matches!(
+            BloomFilter::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(SecretError::BreachCorpus(_))
+        ));
+
+        let corpus = BreachCorpus::from_bloom(filter);
+        assert!(corpus.contains(b"123456"));
+        assert!(!corpus.contains(TEST_SECRET.as_bytes()));
+    }
+
+    #[rstest::rstest]
+    fn test_password_policy_rejects_breached() {
+        let config = SecretConfig {
+            password_policy: Some(PasswordPolicy {
+                breached: Some(BreachCorpus::from_hibp(Cursor::new(HIBP)).expect("invalid corpus")),
+                ..Default::default()
+            }),
+            ..Preset::Testing.into()
+        };
+        assert!(matches!(
+            Secret::new_with_config("password", config.clone()),
+            Err(SecretError::PasswordPolicy(PasswordViolation::Breached))
+        ));
+        assert!(Secret::new_with_config(TEST_SECRET, config).is_ok());
+    }
+}
+
+mod test_normalization {
+    use crate::{Hasher, Normalization, Preset, Secret, SecretConfig, SecretError};
+
+    const PRECOMPOSED: &str = "caf\u{e9}-secret";
+    const DECOMPOSED: &str = "cafe\u{301}-secret";
+
+    fn config(normalization: Normalization) -> SecretConfig {
+        SecretConfig {
+            normalization,
+            ..Preset::Testing.into()
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::nfkc(Normalization::Nfkc, PRECOMPOSED, DECOMPOSED)]
+    #[case::nfkc_compatibility(Normalization::Nfkc, "\u{fb01}le-secret", "file-secret")]
+    #[case::opaque_string(Normalization::OpaqueString, PRECOMPOSED, DECOMPOSED)]
+    #[case::opaque_string_space(Normalization::OpaqueString, "my\u{a0}secret", "my secret")]
+    fn test_normalized_secret(
+        #[case] normalization: Normalization,
+        #[case] hashed: &str,
+        #[case] verified: &str,
+    ) {
+        let secret =
+            Secret::new_with_config(hashed, config(normalization)).expect("invalid secret");
+        assert!(secret.verify(verified));
+
+        let loaded = Secret::load(secret.to_string()).expect("failed to load secret");
+        assert!(loaded.verify(verified));
+        assert!(loaded.verify(hashed));
+        assert_eq!(
+            loaded
+                .secret
+                .config()
+                .expect("invalid config")
+                .normalization,
+            normalization
+        );
+        assert!(!loaded.verify("not-my-secret"));
+    }
+
+    #[rstest::rstest]
+    fn test_unnormalized_secret() {
+        let secret = Secret::new_with_config(PRECOMPOSED, config(Normalization::None))
+            .expect("invalid secret");
+        assert!(!secret.verify(DECOMPOSED));
+        assert!(secret.needs_rehash(&config(Normalization::Nfkc)));
+
+        let secret = Secret::new_with_config(PRECOMPOSED, config(Normalization::Nfkc))
+            .expect("invalid secret");
+        assert!(!secret.needs_rehash(&config(Normalization::Nfkc)));
+    }
+
+    #[rstest::rstest]
+    fn test_hasher_normalization() {
+        let hasher = Hasher::new(config(Normalization::Nfkc));
+        let secret = hasher.hash(PRECOMPOSED).expect("invalid secret");
+        assert!(secret.verify_with(&hasher, DECOMPOSED));
+        assert!(secret.verify(DECOMPOSED));
+    }
+
+    #[rstest::rstest]
+    #[case::empty("")]
+    #[case::control("my\u{7}secret")]
+    fn test_opaque_string_rejects(#[case] secret: &str) {
+        assert!(matches!(
+            Secret::new_with_config(secret, config(Normalization::OpaqueString)),
+            Err(SecretError::Normalization(_))
+        ));
+    }
+}
+
+mod test_pre_hash {
+    use crate::tests::TEST_SECRET;
+    use crate::{Hasher, PasswordPolicy, PreHash, Preset, Secret, SecretConfig, SecretError};
+
+    fn config(pre_hash: PreHash) -> SecretConfig {
+        SecretConfig {
+            pre_hash,
+            ..Preset::Testing.into()
+        }
+    }
+
+    #[cfg(feature = "prehash")]
+    #[rstest::rstest]
+    fn test_pre_hashed_secret() {
+        let long_secret = "long-secret-".repeat(1 << 16);
+        let secret = Secret::new_with_config(&long_secret, config(PreHash::HmacSha512))
+            .expect("invalid secret");
+        assert!(secret.verify(&long_secret));
+        assert!(!secret.verify(&long_secret[1..]));
+
+        let loaded = Secret::load(secret.to_string()).expect("failed to load secret");
+        assert!(loaded.verify(&long_secret));
+        assert_eq!(
+            loaded.secret.config().expect("invalid config").pre_hash,
+            PreHash::HmacSha512
+        );
+        assert!(!loaded.needs_rehash(&config(PreHash::HmacSha512)));
+        assert!(loaded.needs_rehash(&config(PreHash::None)));
+    }
+
+    #[cfg(feature = "prehash")]
+    #[rstest::rstest]
+    fn test_pre_hash_with_normalization() {
+        use crate::Normalization;
+
+        let config = SecretConfig {
+            normalization: Normalization::Nfkc,
+            ..config(PreHash::HmacSha512)
+        };
+        let secret = Secret::new_with_config("caf\u{e9}", config).expect("invalid secret");
+
+        let loaded = Secret::load(secret.to_string()).expect("failed to load secret");
+        let loaded_config = loaded.secret.config().expect("invalid config");
+        assert_eq!(loaded_config.normalization, Normalization::Nfkc);
+        assert_eq!(loaded_config.pre_hash, PreHash::HmacSha512);
+        assert!(loaded.verify("cafe\u{301}"));
+    }
+
+    #[cfg(feature = "prehash")]
+    #[rstest::rstest]
+    fn test_hasher_pre_hash() {
+        let hasher = Hasher::new(config(PreHash::HmacSha512));
+        let secret = hasher.hash(TEST_SECRET).expect("invalid secret");
+        assert!(secret.verify_with(&hasher, TEST_SECRET));
+        assert!(secret.verify(TEST_SECRET));
+    }
+
+    #[cfg(all(feature = "prehash", feature = "bcrypt"))]
+    #[rstest::rstest]
+    fn test_bcrypt_rejects_pre_hash() {
+        use crate::Backend;
+
+        let config = SecretConfig {
+            backend: Backend::Bcrypt { cost: 4 },
+            ..config(PreHash::HmacSha512)
+        };
+        assert!(matches!(
+            Secret::new_with_config(TEST_SECRET, config.clone()),
+            Err(SecretError::UnsupportedByBackend("pre_hash"))
+        ));
+        assert!(matches!(
+            Hasher::new(config).hash(TEST_SECRET),
+            Err(SecretError::UnsupportedByBackend("pre_hash"))
+        ));
+    }
+
+    #[cfg(not(feature = "prehash"))]
+    #[rstest::rstest]
+    fn test_pre_hash_unavailable() {
+        assert!(matches!(
+            Secret::load("$argon2id$v=19$m=8,t=1,p=1,data=aG1hYy1zaGE1MTI$c2FsdHNhbHRzYWx0c2FsdA$EVfkzdbkxEq5wvvajH66helPj12WjcVw4hcGHquNwSk"),
+            Err(SecretError::PreHashUnavailable)
+        ));
+    }
+
+    #[rstest::rstest]
+    fn test_max_input_len() {
+        let config = SecretConfig {
+            max_input_len: Some(TEST_SECRET.len()),
+            ..config(PreHash::None)
+        };
+        let too_long = format!("{TEST_SECRET}!");
+        assert!(matches!(
+            Secret::new_with_config(&too_long, config.clone()),
+            Err(SecretError::InputTooLong(len)) if len == TEST_SECRET.len()
+        ));
+        assert!(matches!(
+            Hasher::new(config.clone()).hash(&too_long),
+            Err(SecretError::InputTooLong(_))
+        ));
+
+        let strict = SecretConfig {
+            password_policy: Some(PasswordPolicy {
+                min_length: 100,
+                ..Default::default()
+            }),
+            ..config.clone()
+        };
+        assert!(matches!(
+            Secret::new_with_config(&too_long, strict.clone()),
+            Err(SecretError::InputTooLong(_))
+        ));
+        assert!(matches!(
+            Secret::wrap_with_config(too_long.clone(), strict.clone()),
+            Err(SecretError::InputTooLong(_))
+        ));
+        assert!(matches!(
+            Hasher::new(strict).hash(&too_long),
+            Err(SecretError::InputTooLong(_))
+        ));
+
+        let secret = Secret::new_with_config(TEST_SECRET, config.clone()).expect("invalid secret");
+        assert!(secret.try_verify_with_config(TEST_SECRET, &config).is_ok());
+        assert!(matches!(
+            secret.try_verify_with_config(&too_long, &config),
+            Err(SecretError::InputTooLong(_))
+        ));
+        assert!(matches!(
+            secret.try_verify(&too_long),
+            Err(SecretError::InputTooLong(_))
+        ));
+        assert!(!secret.verify_with(&Hasher::default(), &too_long));
+
+        let secretized = secret.to_string();
+        let loaded = Secret::load_with_config(&secretized, &config).expect("invalid secret hash");
+        assert!(loaded.verify(TEST_SECRET));
+        assert!(matches!(
+            loaded.try_verify(&too_long),
+            Err(SecretError::InputTooLong(_))
+        ));
+        let attached = Secret::load(&secretized)
+            .and_then(|secret| secret.with_config(&config))
+            .expect("invalid secret hash");
+        assert!(!attached.verify(&too_long));
+    }
+}
+
+mod test_ct_eq {
+    use std::cell::Cell;
+
+    use crate::eq::ct_eq_bytes;
+    use crate::tests::TEST_SECRET;
+    use crate::{ConstantTimeEq, Preset, Secret};
+
+    struct Counting<'a> {
+        bytes: std::slice::Iter<'a, u8>,
+        visited: &'a Cell<usize>,
+    }
+
+    impl Iterator for Counting<'_> {
+        type Item = u8;
+
+        fn next(&mut self) -> Option<u8> {
+            let byte = self.bytes.next().copied();
+            if byte.is_some() {
+                self.visited.set(self.visited.get() + 1);
+            }
+            byte
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.bytes.size_hint()
+        }
+    }
+
+    impl ExactSizeIterator for Counting<'_> {}
+
+    #[rstest::rstest]
+    #[case::first_byte(b"$argon2id$v=19$aaaa", b"#argon2id$v=19$aaaa", false)]
+    #[case::last_byte(b"$argon2id$v=19$aaaa", b"$argon2id$v=19$aaab", false)]
+    #[case::equal(b"$argon2id$v=19$aaaa", b"$argon2id$v=19$aaaa", true)]
+    fn test_ct_eq_does_not_short_circuit(
+        #[case] lhs: &[u8],
+        #[case] rhs: &[u8],
+        #[case] equal: bool,
+    ) {
+        let (lhs_visited, rhs_visited) = (Cell::new(0), Cell::new(0));
+        let choice = ct_eq_bytes(
+            Counting {
+                bytes: lhs.iter(),
+                visited: &lhs_visited,
+            },
+            Counting {
+                bytes: rhs.iter(),
+                visited: &rhs_visited,
+            },
+        );
+        assert_eq!(bool::from(choice), equal);
+        assert_eq!(lhs_visited.get(), lhs.len());
+        assert_eq!(rhs_visited.get(), rhs.len());
+    }
+
+    #[rstest::rstest]
+    fn test_secret_ct_eq() {
+        let secret =
+            Secret::new_with_config(TEST_SECRET, Preset::Testing.into()).expect("invalid secret");
+        let loaded = Secret::load(secret.to_string()).expect("failed to load secret");
+        let other =
+            Secret::new_with_config(TEST_SECRET, Preset::Testing.into()).expect("invalid secret");
+
+        assert!(bool::from(secret.ct_eq(&loaded)));
+        assert!(!bool::from(secret.ct_eq(&other)));
+        assert!(secret == loaded);
+        assert!(secret != other);
+    }
+}
+
+mod test_hash_ord {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::hash::{Hash, Hasher};
+
+    use crate::tests::TEST_SECRET;
+    use crate::{Preset, Secret};
+
+    fn hash_of(secret: &Secret) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        secret.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn testing_secret() -> Secret {
+        Secret::new_with_config(TEST_SECRET, Preset::Testing.into()).expect("invalid secret")
+    }
+
+    #[rstest::rstest]
+    fn test_loaded_secret_hashes_identically() {
+        let secret = testing_secret();
+        let loaded = Secret::load(secret.to_string()).expect("failed to load secret");
+        assert_eq!(secret, loaded);
+        assert_eq!(hash_of(&secret), hash_of(&loaded));
+
+        let credentials = HashSet::from([secret.clone(), loaded]);
+        assert_eq!(credentials.len(), 1);
+
+        let cache = HashMap::from([(secret.clone(), "api-key")]);
+        let loaded = Secret::load(secret.to_string()).expect("failed to load secret");
+        assert_eq!(cache.get(&loaded), Some(&"api-key"));
+    }
+
+    #[rstest::rstest]
+    fn test_reordered_params_are_equal() {
+        let secret = testing_secret();
+        let secretized = secret.to_string();
+        assert!(secretized.contains("$m=8,t=1,p=1$"));
+        let reordered = Secret::load(secretized.replace("$m=8,t=1,p=1$", "$p=1,t=1,m=8$"))
+            .expect("failed to load secret");
+
+        assert_ne!(reordered.to_string(), secretized);
+        assert_eq!(reordered, secret);
+        assert_eq!(hash_of(&reordered), hash_of(&secret));
+        assert_eq!(reordered.cmp(&secret), std::cmp::Ordering::Equal);
+        assert!(reordered.verify(TEST_SECRET));
+    }
+
+    #[rstest::rstest]
+    fn test_secret_ordering() {
+        let secrets = [testing_secret(), testing_secret(), testing_secret()];
+        let mut sorted = secrets.to_vec();
+        sorted.sort();
+        let mut reversed = secrets.iter().rev().cloned().collect::<Vec<_>>();
+        reversed.sort();
+        assert_eq!(sorted, reversed);
+        assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let set = secrets.iter().cloned().collect::<BTreeSet<_>>();
+        assert_eq!(set.len(), secrets.len());
+        assert_ne!(secrets[0], secrets[1]);
+        assert_ne!(hash_of(&secrets[0]), hash_of(&secrets[1]));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{Secret, SecretConfig, SecretResult, Verification};
+
+#[derive(Debug)]
+pub enum Upgrade {
+    Mismatch,
+    Current,
+    Upgraded(Secret),
+}
+
+impl Secret {
+    pub fn needs_rehash(&self, target: &SecretConfig) -> bool {
+        match self.secret.config() {
+            Ok(config) => config.is_weaker_than(target),
+            Err(_) => true,
+        }
+    }
+
+    pub fn verify_and_upgrade<S: AsRef<[u8]>>(
+        &self,
+        secret: S,
+        target: &SecretConfig,
+    ) -> SecretResult<Upgrade> {
+        match self.try_verify_with_config(secret.as_ref(), target)? {
+            Verification::Mismatch => Ok(Upgrade::Mismatch),
+            Verification::Match => Ok(Upgrade::Current),
+            Verification::NeedsRehash => {
+                Self::hash_secret(secret.as_ref(), target.clone()).map(Upgrade::Upgraded)
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{Secret, SecretConfig, SecretResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    Match,
+    Mismatch,
+    NeedsRehash,
+}
+
+impl Verification {
+    pub fn is_match(&self) -> bool {
+        !matches!(self, Verification::Mismatch)
+    }
+}
+
+impl Secret {
+    /// Never reports `NeedsRehash`, use `try_verify_with_config` to compare against a target.
+    pub fn try_verify<S: AsRef<[u8]>>(&self, secret: S) -> SecretResult<Verification> {
+        self.check_input_len(secret.as_ref())?;
+        if self
+            .secret
+            .verify(secret.as_ref(), self.verifying_pepper()?)?
+        {
+            Ok(Verification::Match)
+        } else {
+            Ok(Verification::Mismatch)
+        }
+    }
+
+    pub fn try_verify_with_config<S: AsRef<[u8]>>(
+        &self,
+        secret: S,
+        target: &SecretConfig,
+    ) -> SecretResult<Verification> {
+        target.check_input_len(secret.as_ref())?;
+        match self.try_verify(secret)? {
+            Verification::Match if self.secret.config()?.is_weaker_than(target) => {
+                Ok(Verification::NeedsRehash)
+            }
+            verification => Ok(verification),
+        }
+    }
+}
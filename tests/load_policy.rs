@@ -0,0 +1,38 @@
+// Installs the process-wide policy, so it runs in its own test binary.
+use secretize::{Algorithm, LoadPolicy, Preset, Secret, SecretConfig, SecretError};
+
+const TEST_SECRET: &str = "my-secret";
+
+#[rstest::rstest]
+fn test_installed_load_policy() {
+    let policy = LoadPolicy {
+        algorithm: Algorithm::Argon2i,
+        ..Default::default()
+    };
+    policy.clone().install().expect("policy already installed");
+    assert!(matches!(
+        policy.install(),
+        Err(SecretError::LoadPolicyAlreadyInstalled)
+    ));
+
+    let config = SecretConfig {
+        algorithm: Algorithm::Argon2d,
+        params: Preset::Testing.params(),
+        ..Default::default()
+    };
+    let secretized = Secret::new_with_config(TEST_SECRET, config)
+        .expect("invalid secret")
+        .to_string();
+    assert!(matches!(
+        Secret::load(&secretized),
+        Err(SecretError::LoadPolicy("algorithm"))
+    ));
+    assert!(secretized.parse::<Secret>().is_err());
+
+    let config = SecretConfig {
+        load_policy: Some(LoadPolicy::default()),
+        ..Default::default()
+    };
+    let secret = Secret::load_with_config(&secretized, &config).expect("invalid secret hash");
+    assert!(secret.verify(TEST_SECRET));
+}
@@ -0,0 +1,42 @@
+// Installs the process-wide policy, so it runs in its own test binary.
+use secretize::{PasswordPolicy, PasswordViolation, Preset, Secret, SecretConfig, SecretError};
+
+const TEST_SECRET: &str = "my-secret";
+
+#[rstest::rstest]
+fn test_installed_password_policy() {
+    let policy = PasswordPolicy {
+        banned: vec!["hunter2".to_string()],
+        ..Default::default()
+    };
+    policy.clone().install().expect("policy already installed");
+    assert!(matches!(
+        policy.install(),
+        Err(SecretError::PasswordPolicyAlreadyInstalled)
+    ));
+
+    assert!(matches!(
+        Secret::new("hunter2"),
+        Err(SecretError::PasswordPolicy(PasswordViolation::Banned))
+    ));
+    assert!(Secret::new(TEST_SECRET).is_ok());
+
+    let config = SecretConfig {
+        password_policy: Some(PasswordPolicy::default()),
+        ..Preset::Testing.into()
+    };
+    assert!(Secret::new_with_config("hunter2", config).is_ok());
+
+    #[cfg(feature = "openapi")]
+    {
+        use poem_openapi::types::ParseFromJSON;
+
+        let message = Secret::parse_from_json(Some(serde_json::json!({ "secret": "hunter2" })))
+            .expect_err("password is allowed")
+            .into_message();
+        assert!(message.contains("banned password"), "{message}");
+        assert!(
+            secretize::PlainSecret::parse_from_json(Some(serde_json::json!("hunter2"))).is_err()
+        );
+    }
+}
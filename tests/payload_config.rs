@@ -0,0 +1,62 @@
+// Installs the process-wide payload config, so it runs in its own test binary.
+use secretize::{
+    Algorithm, LoadPolicy, Pepper, PlainSecret, Preset, Secret, SecretConfig, SecretError,
+    StoredSecret,
+};
+
+const TEST_SECRET: &str = "my-secret";
+
+#[rstest::rstest]
+fn test_installed_payload_config() {
+    let config = SecretConfig {
+        pepper: Some(Pepper::new("my-pepper")),
+        load_policy: Some(LoadPolicy {
+            algorithm: Algorithm::Argon2id,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    config
+        .clone()
+        .install_payload()
+        .expect("payload config already installed");
+    assert!(matches!(
+        SecretConfig::default().install_payload(),
+        Err(SecretError::PayloadConfigAlreadyInstalled)
+    ));
+
+    let plain = PlainSecret::new(TEST_SECRET).expect("invalid secret");
+    let secretized = plain.as_ref().to_string();
+    assert!(!Secret::load(&secretized)
+        .expect("invalid secret hash")
+        .verify(TEST_SECRET));
+
+    let stored = StoredSecret::load(&secretized).expect("invalid secret hash");
+    assert!(stored.as_ref().verify(TEST_SECRET));
+
+    let weak = Secret::new_with_config(
+        TEST_SECRET,
+        SecretConfig {
+            algorithm: Algorithm::Argon2d,
+            ..Preset::Testing.into()
+        },
+    )
+    .expect("invalid secret");
+    assert!(matches!(
+        StoredSecret::load(weak.to_string()),
+        Err(SecretError::LoadPolicy("algorithm"))
+    ));
+
+    #[cfg(feature = "serde")]
+    {
+        let serialized = serde_json::to_string(&weak).expect("failed to serialize secret");
+        let err = serde_json::from_str::<StoredSecret>(&serialized)
+            .expect_err("weak secret hash is allowed");
+        assert!(err.to_string().contains("load policy"), "{err}");
+
+        let serialized = serde_json::to_string(&stored).expect("failed to serialize secret");
+        let deserialized =
+            serde_json::from_str::<StoredSecret>(&serialized).expect("invalid secret hash");
+        assert!(deserialized.as_ref().verify(TEST_SECRET));
+    }
+}